@@ -1,4 +1,4 @@
-use btree_graph::error::Error;
+use btree_graph::Error;
 use btree_graph::*;
 use criterion::{black_box, Criterion};
 
@@ -149,6 +149,29 @@ pub fn add_edge_benchmark(c: &mut Criterion) {
     });
 }
 
+/// A star graph; vertex 0 is the hub with an edge to each of the spokes.
+fn setup_star(spokes: usize) -> BTreeGraph<usize, usize> {
+    BTreeGraph::from_edges((1..=spokes).map(|spoke| (spoke, (0, spoke))))
+}
+
+pub fn star_benchmark(c: &mut Criterion) {
+    let spokes: usize = 100_000;
+    let mut graph = setup_star(spokes);
+    c.bench_function("graph::api::AddEdge (star graph hub, 100k spokes)", |b| {
+        b.iter(|| black_box(graph.add_edge(0, 1, spokes + 1)))
+    });
+
+    c.bench_function(
+        "graph::api::RemoveEdge (star graph hub, 100k spokes, edge re-added)",
+        |b| {
+            b.iter(|| {
+                graph.add_edge(0, 1, spokes + 1).unwrap();
+                black_box(graph.remove_edge(spokes + 1))
+            })
+        },
+    );
+}
+
 pub fn get_edge_value_benchmark(c: &mut Criterion) {
     let graph = setup().unwrap();
     c.bench_function("graph::api::GetEdgeValue (edge does not exist)", |b| {
@@ -226,3 +249,18 @@ pub fn connections_benchmark(c: &mut Criterion) {
         b.iter(|| black_box(graph.connections(String::from("9"))))
     });
 }
+
+pub fn frozen_benchmark(c: &mut Criterion) {
+    let frozen = setup().unwrap().freeze();
+    c.bench_function("graph::frozen::Adjacent (vertices are adjacent)", |b| {
+        b.iter(|| black_box(frozen.adjacent(String::from("0"), String::from("1"))))
+    });
+
+    c.bench_function("graph::frozen::Connections (vertex exists)", |b| {
+        b.iter(|| black_box(frozen.connections(String::from("0"))))
+    });
+
+    c.bench_function("graph::frozen::GetEdgeValue (edge exists)", |b| {
+        b.iter(|| black_box(frozen.get_edge_value(1)))
+    });
+}
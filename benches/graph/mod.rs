@@ -15,5 +15,7 @@ criterion_group!(
     remove_edge_benchmark,
     remove_vertex_benchmark,
     adjacent_benchmark,
-    connections_benchmark
+    connections_benchmark,
+    star_benchmark,
+    frozen_benchmark
 );
@@ -1,5 +1,5 @@
 use alloc::collections::BTreeSet;
-use crate::Edge;
+use crate::{Conflict, ConflictPolicy, Edge, GraphDiff, RemovedEdges};
 
 /// `Vertices` returns the set of the vertices which comprise the graph.
 ///
@@ -35,24 +35,49 @@ where
     fn edges(&self) -> BTreeSet<&T>;
 }
 
-/// `AddVertex` adds the vertex x, if it is not there.
+/// `AddVertex` adds the vertex x, if it is not there. Returns whether the vertex was
+/// added; adding a vertex which is there leaves it, and its edges untouched.
 ///
 /// # Example
 ///
 /// ```
 /// use btree_graph::{BTreeGraph, AddVertex, Vertices};
-/// use std::collections::BTreeSet;
 /// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
-/// let old_vertex_value: Option<BTreeSet<usize>> = graph.add_vertex(String::from("origin"));
 ///
-/// assert!(old_vertex_value.is_none());
+/// assert!(graph.add_vertex(String::from("origin")));
+/// assert!(!graph.add_vertex(String::from("origin")));
 /// assert_eq!(graph.vertices().len(), 1)
 /// ```
-pub trait AddVertex<V, E>
+pub trait AddVertex<V> {
+    fn add_vertex(&mut self, x: V) -> bool;
+}
+
+/// `ResetVertex` removes every edge from the vertex x, adding x if it is not there. Edges
+/// to x from other vertices are kept. Returns the edges removed, or none if the vertex
+/// was not there.
+///
+/// # Example
+///
+/// ```
+/// use btree_graph::{BTreeGraph, AddVertex, AddEdge, Edges, ResetVertex};
+/// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+/// graph.add_vertex(String::from("origin"));
+/// graph.add_vertex(String::from("destination"));
+/// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+/// graph.add_edge(String::from("destination"), String::from("origin"), 20);
+///
+/// let removed = graph.reset_vertex(String::from("origin")).unwrap();
+///
+/// assert!(removed.contains(&(10, (String::from("origin"), String::from("destination")))));
+/// assert_eq!(graph.edges().len(), 1);
+/// assert!(graph.reset_vertex(String::from("island")).is_none());
+/// ```
+pub trait ResetVertex<V, E>
 where
+    V: Ord,
     E: Ord,
 {
-    fn add_vertex(&mut self, x: V) -> Option<BTreeSet<E>>;
+    fn reset_vertex(&mut self, x: V) -> Option<BTreeSet<Edge<V, E>>>;
 }
 
 /// `AddEdge` add an edge from the vertex x to the vertex y, if it is not there.
@@ -74,6 +99,25 @@ pub trait AddEdge<V, E> {
     fn add_edge(&mut self, x: V, y: V, e: E) -> Result<Option<(V, V)>, Self::Error>;
 }
 
+/// `AddEdgeWithVertices` adds an edge from the vertex x to the vertex y, first adding x,
+/// and y if they are not there. If the edge is there, its value is replaced, and the old
+/// value returned.
+///
+/// # Example
+///
+/// ```
+/// use btree_graph::{BTreeGraph, AddEdgeWithVertices, Edges, Vertices};
+/// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+/// let old_edge_value: Option<(String, String)> = graph.add_edge_with_vertices(String::from("origin"), String::from("destination"), 10);
+///
+/// assert!(old_edge_value.is_none());
+/// assert_eq!(graph.vertices().len(), 2);
+/// assert_eq!(graph.edges().len(), 1)
+/// ```
+pub trait AddEdgeWithVertices<V, E> {
+    fn add_edge_with_vertices(&mut self, x: V, y: V, e: E) -> Option<(V, V)>;
+}
+
 /// `GetEdgeValue` returns the value associated with the edge (x, y).
 ///
 /// # Example
@@ -145,24 +189,26 @@ pub trait RemoveEdge<V, E> {
 }
 
 /// `RemoveVertex` removes the vertex x, if it is there. If the vertex does not exist,
-/// an error is raised.
+/// an error is raised. Every edge incident to the vertex is removed along with it, and
+/// returned by direction: incoming, outgoing, and self-loops.
 ///
 /// # Example
 ///
 /// ```
-/// extern crate alloc;
-/// use alloc::collections::btree_set::BTreeSet;
 /// use btree_graph::{BTreeGraph, AddVertex, AddEdge, Edges, RemoveVertex, GetVertexValue, Vertices};
 /// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
 /// graph.add_vertex(String::from("origin"));
 /// graph.add_vertex(String::from("destination"));
 /// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+/// graph.add_edge(String::from("destination"), String::from("origin"), 20);
+/// graph.add_edge(String::from("destination"), String::from("destination"), 30);
 ///
+/// let removed = graph.remove_vertex(String::from("destination")).unwrap();
 ///
-/// let adjacent_edges_removed = graph.remove_vertex(String::from("destination")).unwrap();
-/// let adjacent_edges_removed_vec: Vec<(usize, (String, String))> = adjacent_edges_removed.into_iter().collect();
-///
-/// assert_eq!(adjacent_edges_removed_vec[0], (10, (String::from("origin"), String::from("destination"))));
+/// assert_eq!(removed.len(), 3);
+/// assert!(removed.incoming.contains(&(10, (String::from("origin"), String::from("destination")))));
+/// assert!(removed.outgoing.contains(&(20, (String::from("destination"), String::from("origin")))));
+/// assert!(removed.loops.contains(&(30, (String::from("destination"), String::from("destination")))));
 /// assert_eq!(graph.vertices().len(), 1);
 ///
 /// // Note: removing a vertex will also cascade delete any incident edges, which will then
@@ -172,10 +218,36 @@ pub trait RemoveEdge<V, E> {
 /// ```
 pub trait RemoveVertex<V, E>
 where
+    V: Ord,
     E: Ord,
 {
     type Error;
-    fn remove_vertex(&mut self, x: V) -> Result<BTreeSet<Edge<V, E>>, Self::Error>;
+    fn remove_vertex(&mut self, x: V) -> Result<RemovedEdges<V, E>, Self::Error>;
+}
+
+/// `TryRemoveVertex` removes the vertex x only if no edge leaves, or enters it. If the
+/// vertex does not exist, or an edge is still incident to it, an error is raised and the
+/// graph is left untouched.
+///
+/// # Example
+///
+/// ```
+/// use btree_graph::{BTreeGraph, AddVertex, AddEdge, Error, RemoveEdge, TryRemoveVertex, Vertices};
+/// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+/// graph.add_vertex(String::from("origin"));
+/// graph.add_vertex(String::from("destination"));
+/// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+///
+/// assert_eq!(graph.try_remove_vertex(String::from("destination")).unwrap_err(), Error::EdgeExists);
+/// assert_eq!(graph.vertices().len(), 2);
+///
+/// graph.remove_edge(10);
+/// graph.try_remove_vertex(String::from("destination")).unwrap();
+/// assert_eq!(graph.vertices().len(), 1);
+/// ```
+pub trait TryRemoveVertex<V> {
+    type Error;
+    fn try_remove_vertex(&mut self, x: V) -> Result<(), Self::Error>;
 }
 
 /// `Adjacent` tests whether there is an edge from the vertex x to the vertex y.
@@ -221,3 +293,387 @@ pub trait Connections<T> {
     type Error;
     fn connections(&self, x: T) -> Result<BTreeSet<&T>, Self::Error>;
 }
+
+/// `Sources` lists all vertices x such that there is no edge to the vertex x, i.e. every
+/// vertex with an in-degree of zero. The targets of all edges are collected before the first
+/// vertex is yielded; the vertices are then yielded in ascending order.
+///
+/// # Example
+///
+/// ```
+/// use btree_graph::{BTreeGraph, AddVertex, AddEdge, Sources};
+/// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+/// graph.add_vertex(String::from("origin"));
+/// graph.add_vertex(String::from("destination"));
+/// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+///
+/// let sources: Vec<&String> = graph.sources().collect();
+/// assert_eq!(sources, vec![&String::from("origin")]);
+/// ```
+pub trait Sources<T> {
+    fn sources<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a;
+}
+
+/// `Sinks` lists all vertices x such that there is no edge from the vertex x, i.e. every
+/// vertex with an out-degree of zero. The vertices are yielded lazily in ascending order.
+///
+/// # Example
+///
+/// ```
+/// use btree_graph::{BTreeGraph, AddVertex, AddEdge, Sinks};
+/// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+/// graph.add_vertex(String::from("origin"));
+/// graph.add_vertex(String::from("destination"));
+/// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+///
+/// let sinks: Vec<&String> = graph.sinks().collect();
+/// assert_eq!(sinks, vec![&String::from("destination")]);
+/// ```
+pub trait Sinks<T> {
+    fn sinks<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a;
+}
+
+/// `IsolatedVertices` lists all vertices x such that there is neither an edge to, nor an
+/// edge from the vertex x. Note: a vertex with a self-loop is not isolated. The targets of all
+/// edges are collected before the first vertex is yielded.
+///
+/// # Example
+///
+/// ```
+/// use btree_graph::{BTreeGraph, AddVertex, AddEdge, IsolatedVertices};
+/// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+/// graph.add_vertex(String::from("origin"));
+/// graph.add_vertex(String::from("destination"));
+/// graph.add_vertex(String::from("island"));
+/// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+///
+/// let isolated: Vec<&String> = graph.isolated_vertices().collect();
+/// assert_eq!(isolated, vec![&String::from("island")]);
+/// ```
+pub trait IsolatedVertices<T> {
+    fn isolated_vertices<'a>(&'a self) -> impl Iterator<Item = &'a T>
+    where
+        T: 'a;
+}
+
+/// `RemoveIsolatedVertices` removes every isolated vertex from the graph and returns
+/// the set of vertices which were removed.
+///
+/// # Example
+///
+/// ```
+/// use btree_graph::{BTreeGraph, AddVertex, AddEdge, RemoveIsolatedVertices, Vertices};
+/// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+/// graph.add_vertex(String::from("origin"));
+/// graph.add_vertex(String::from("destination"));
+/// graph.add_vertex(String::from("island"));
+/// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+///
+/// let removed = graph.remove_isolated_vertices();
+/// assert!(removed.contains(&String::from("island")));
+/// assert_eq!(graph.vertices().len(), 2);
+/// ```
+pub trait RemoveIsolatedVertices<T>
+where
+    T: Ord,
+{
+    fn remove_isolated_vertices(&mut self) -> BTreeSet<T>;
+}
+
+/// `InducedSubgraph` returns the subgraph induced by the given set of vertices, i.e. the
+/// graph made up of those vertices and every edge whose endpoints are both in the set.
+/// Vertices in the set which do not exist in the graph are ignored.
+///
+/// # Example
+///
+/// ```
+/// extern crate alloc;
+/// use alloc::collections::btree_set::BTreeSet;
+/// use btree_graph::{BTreeGraph, AddVertex, AddEdge, Edges, InducedSubgraph, Vertices};
+/// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+/// graph.add_vertex(String::from("origin"));
+/// graph.add_vertex(String::from("waypoint"));
+/// graph.add_vertex(String::from("destination"));
+/// graph.add_edge(String::from("origin"), String::from("waypoint"), 10);
+/// graph.add_edge(String::from("waypoint"), String::from("destination"), 20);
+///
+/// let mut vertices: BTreeSet<String> = BTreeSet::new();
+/// vertices.insert(String::from("origin"));
+/// vertices.insert(String::from("waypoint"));
+/// let subgraph = graph.induced_subgraph(&vertices);
+///
+/// assert_eq!(subgraph.vertices().len(), 2);
+/// assert!(subgraph.edges().contains(&10));
+/// assert!(!subgraph.edges().contains(&20));
+/// ```
+pub trait InducedSubgraph<T>
+where
+    T: Ord,
+{
+    fn induced_subgraph(&self, x: &BTreeSet<T>) -> Self;
+}
+
+/// `EdgeSubgraph` returns the subgraph made up of the given set of edges and their
+/// endpoints. Edges in the set which do not exist in the graph are ignored.
+///
+/// # Example
+///
+/// ```
+/// extern crate alloc;
+/// use alloc::collections::btree_set::BTreeSet;
+/// use btree_graph::{BTreeGraph, AddVertex, AddEdge, EdgeSubgraph, Edges, Vertices};
+/// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+/// graph.add_vertex(String::from("origin"));
+/// graph.add_vertex(String::from("waypoint"));
+/// graph.add_vertex(String::from("destination"));
+/// graph.add_edge(String::from("origin"), String::from("waypoint"), 10);
+/// graph.add_edge(String::from("waypoint"), String::from("destination"), 20);
+///
+/// let mut edges: BTreeSet<usize> = BTreeSet::new();
+/// edges.insert(20);
+/// let subgraph = graph.edge_subgraph(&edges);
+///
+/// assert_eq!(subgraph.vertices().len(), 2);
+/// assert!(!subgraph.vertices().contains(&String::from("origin")));
+/// assert_eq!(subgraph.edges().len(), 1);
+/// ```
+pub trait EdgeSubgraph<T>
+where
+    T: Ord,
+{
+    fn edge_subgraph(&self, x: &BTreeSet<T>) -> Self;
+}
+
+/// `FilterMap` returns the subgraph made up of every vertex accepted by the vertex
+/// predicate, and every edge accepted by the edge predicate whose endpoints were both
+/// accepted.
+///
+/// # Example
+///
+/// ```
+/// use btree_graph::{BTreeGraph, AddVertex, AddEdge, Edges, FilterMap, Vertices};
+/// let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+/// graph.add_vertex(0);
+/// graph.add_vertex(1);
+/// graph.add_vertex(2);
+/// graph.add_edge(0, 1, 10);
+/// graph.add_edge(1, 2, 20);
+/// graph.add_edge(0, 2, 30);
+///
+/// let subgraph = graph.filter_map(|v| *v != 1, |e, _| *e > 10);
+///
+/// assert_eq!(subgraph.vertices().len(), 2);
+/// assert_eq!(subgraph.edges().into_iter().collect::<Vec<&usize>>(), vec![&30]);
+/// ```
+pub trait FilterMap<V, E> {
+    fn filter_map<P, Q>(&self, vertex_pred: P, edge_pred: Q) -> Self
+    where
+        P: FnMut(&V) -> bool,
+        Q: FnMut(&E, &(V, V)) -> bool;
+}
+
+/// `Union` returns the graph made up of every vertex, and every edge found in either
+/// graph. An edge key which maps to different vertices in the two graphs is a conflict;
+/// conflicts are resolved by the policy and returned alongside the result.
+///
+/// # Example
+///
+/// ```
+/// use btree_graph::{BTreeGraph, AddVertex, AddEdge, ConflictPolicy, Edges, GetEdgeValue, Union, Vertices};
+/// let mut left: BTreeGraph<usize, usize> = BTreeGraph::new();
+/// left.add_vertex(0);
+/// left.add_vertex(1);
+/// left.add_edge(0, 1, 10);
+///
+/// let mut right: BTreeGraph<usize, usize> = BTreeGraph::new();
+/// right.add_vertex(1);
+/// right.add_vertex(2);
+/// right.add_edge(1, 2, 10);
+/// right.add_edge(1, 2, 20);
+///
+/// let (union, conflicts) = left.union(&right, ConflictPolicy::Left);
+/// assert_eq!(union.vertices().len(), 3);
+/// assert_eq!(union.edges().len(), 2);
+/// assert_eq!(union.get_edge_value(10).unwrap(), &(0, 1));
+/// assert!(conflicts.contains(&(10, ((0, 1), (1, 2)))));
+/// ```
+pub trait Union<V, E>
+where
+    Self: Sized,
+    V: Ord,
+    E: Ord,
+{
+    fn union(&self, other: &Self, policy: ConflictPolicy) -> (Self, BTreeSet<Conflict<V, E>>);
+}
+
+/// `Intersection` returns the graph made up of every vertex, and every edge found in both
+/// graphs. Edges resolved from a conflict are only kept when both of their vertices are
+/// in the intersection.
+///
+/// # Example
+///
+/// ```
+/// use btree_graph::{BTreeGraph, AddVertex, AddEdge, ConflictPolicy, Edges, Intersection, Vertices};
+/// let mut left: BTreeGraph<usize, usize> = BTreeGraph::new();
+/// left.add_vertex(0);
+/// left.add_vertex(1);
+/// left.add_edge(0, 1, 10);
+///
+/// let mut right: BTreeGraph<usize, usize> = BTreeGraph::new();
+/// right.add_vertex(0);
+/// right.add_vertex(1);
+/// right.add_vertex(2);
+/// right.add_edge(0, 1, 10);
+/// right.add_edge(1, 2, 20);
+///
+/// let (intersection, conflicts) = left.intersection(&right, ConflictPolicy::Exclude);
+/// assert_eq!(intersection, left);
+/// assert!(conflicts.is_empty());
+/// ```
+pub trait Intersection<V, E>
+where
+    Self: Sized,
+    V: Ord,
+    E: Ord,
+{
+    fn intersection(
+        &self,
+        other: &Self,
+        policy: ConflictPolicy,
+    ) -> (Self, BTreeSet<Conflict<V, E>>);
+}
+
+/// `Difference` returns the graph made up of every vertex, and every edge found in this
+/// graph but not the other, along with the vertices of those edges. The right value of a
+/// conflicting edge is never a part of the difference, so `ConflictPolicy::Right` leaves
+/// the edge out just as `ConflictPolicy::Exclude` does.
+///
+/// # Example
+///
+/// ```
+/// use btree_graph::{BTreeGraph, AddVertex, AddEdge, ConflictPolicy, Difference, Edges, Vertices};
+/// let mut left: BTreeGraph<usize, usize> = BTreeGraph::new();
+/// left.add_vertex(0);
+/// left.add_vertex(1);
+/// left.add_vertex(2);
+/// left.add_edge(0, 1, 10);
+/// left.add_edge(1, 2, 20);
+///
+/// let mut right: BTreeGraph<usize, usize> = BTreeGraph::new();
+/// right.add_vertex(0);
+/// right.add_vertex(1);
+/// right.add_edge(0, 1, 10);
+///
+/// let (difference, _) = left.difference(&right, ConflictPolicy::Exclude);
+/// assert!(difference.edges().contains(&20));
+/// assert_eq!(difference.edges().len(), 1);
+/// // Vertex 1 is brought along as an endpoint of edge 20.
+/// assert_eq!(difference.vertices().len(), 2);
+/// ```
+pub trait Difference<V, E>
+where
+    Self: Sized,
+    V: Ord,
+    E: Ord,
+{
+    fn difference(&self, other: &Self, policy: ConflictPolicy) -> (Self, BTreeSet<Conflict<V, E>>);
+}
+
+/// `SymmetricDifference` returns the graph made up of every vertex, and every edge found
+/// in exactly one of the graphs, along with the vertices of those edges.
+///
+/// # Example
+///
+/// ```
+/// use btree_graph::{BTreeGraph, AddVertex, AddEdge, ConflictPolicy, Edges, SymmetricDifference};
+/// let mut left: BTreeGraph<usize, usize> = BTreeGraph::new();
+/// left.add_vertex(0);
+/// left.add_vertex(1);
+/// left.add_edge(0, 1, 10);
+/// left.add_edge(1, 0, 20);
+///
+/// let mut right: BTreeGraph<usize, usize> = BTreeGraph::new();
+/// right.add_vertex(0);
+/// right.add_vertex(1);
+/// right.add_edge(0, 1, 10);
+/// right.add_edge(0, 0, 30);
+///
+/// let (symmetric_difference, _) = left.symmetric_difference(&right, ConflictPolicy::Exclude);
+/// assert_eq!(symmetric_difference.edges().len(), 2);
+/// assert!(!symmetric_difference.edges().contains(&10));
+/// ```
+pub trait SymmetricDifference<V, E>
+where
+    Self: Sized,
+    V: Ord,
+    E: Ord,
+{
+    fn symmetric_difference(
+        &self,
+        other: &Self,
+        policy: ConflictPolicy,
+    ) -> (Self, BTreeSet<Conflict<V, E>>);
+}
+
+/// `Diff` returns the structural difference between this graph and the other: the vertices,
+/// and edges added or removed, and the edges whose vertices changed.
+///
+/// # Example
+///
+/// ```
+/// use btree_graph::{BTreeGraph, AddVertex, AddEdge, Diff};
+/// let mut before: BTreeGraph<String, usize> = BTreeGraph::new();
+/// before.add_vertex(String::from("origin"));
+/// before.add_vertex(String::from("destination"));
+///
+/// let mut after = before.clone();
+/// after.add_edge(String::from("origin"), String::from("destination"), 10);
+///
+/// let diff = before.diff(&after);
+/// assert!(diff.added_edges.contains_key(&10));
+/// assert!(diff.added_vertices.is_empty());
+/// assert!(after.diff(&after).is_empty());
+/// ```
+pub trait Diff<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn diff(&self, other: &Self) -> GraphDiff<V, E>;
+}
+
+/// `ApplyDiff` replays a diff onto the graph. An error is raised, and the graph is left
+/// untouched, if the diff does not apply: a vertex to be removed does not exist, a vertex
+/// to be added already exists, an edge to be removed or changed does not have the recorded
+/// value, an edge to be added already exists, or an edge would be left without a vertex.
+///
+/// # Example
+///
+/// ```
+/// use btree_graph::{BTreeGraph, AddVertex, AddEdge, ApplyDiff, Diff};
+/// let mut before: BTreeGraph<String, usize> = BTreeGraph::new();
+/// before.add_vertex(String::from("origin"));
+///
+/// let mut after = before.clone();
+/// after.add_vertex(String::from("destination"));
+/// after.add_edge(String::from("origin"), String::from("destination"), 10);
+///
+/// let diff = before.diff(&after);
+/// before.apply(&diff).unwrap();
+/// assert_eq!(before, after);
+///
+/// // The diff no longer applies since the edge now exists.
+/// assert!(before.apply(&diff).is_err());
+/// ```
+pub trait ApplyDiff<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    type Error;
+    fn apply(&mut self, diff: &GraphDiff<V, E>) -> Result<(), Self::Error>;
+}
@@ -0,0 +1,350 @@
+//! A compact binary encoding of `BTreeGraph`, for targets without serde, such as graphs
+//! stored in flash. Vertices, and edges are encoded by the `Encode`, and `Decode` traits.
+//!
+//! The encoding is a four byte magic number `BTGR`, and a version byte, followed by two
+//! sections, each prefixed by its length in bytes: the vertices, in ascending order, and
+//! the edges, each with the indices of its source, and target vertex. It ends with a
+//! CRC-32 checksum of everything before it. Lengths, counts, and indices are LEB128
+//! variable length integers; each vertex, and edge is prefixed by its length.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::BTreeGraph;
+
+const MAGIC: &[u8; 4] = b"BTGR";
+const VERSION: u8 = 1;
+
+/// `Encode` writes a vertex, or edge as bytes for `BTreeGraph::to_bytes`.
+pub trait Encode {
+    fn encode(&self, out: &mut Vec<u8>);
+}
+
+/// `Decode` reads a vertex, or edge from exactly the bytes written by `Encode`, or returns
+/// `None` if they are not valid.
+///
+/// # Example
+///
+/// ```
+/// use btree_graph::{Decode, Encode};
+///
+/// #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+/// struct Sensor(u16);
+///
+/// impl Encode for Sensor {
+///     fn encode(&self, out: &mut Vec<u8>) {
+///         self.0.encode(out);
+///     }
+/// }
+///
+/// impl Decode for Sensor {
+///     fn decode(bytes: &[u8]) -> Option<Self> {
+///         u16::decode(bytes).map(Sensor)
+///     }
+/// }
+///
+/// let mut bytes = Vec::new();
+/// Sensor(7).encode(&mut bytes);
+/// assert_eq!(Sensor::decode(&bytes), Some(Sensor(7)));
+/// ```
+pub trait Decode: Sized {
+    fn decode(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(
+            /// Integers are encoded as little endian bytes.
+            impl Encode for $t {
+                fn encode(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+            }
+
+            impl Decode for $t {
+                fn decode(bytes: &[u8]) -> Option<Self> {
+                    let mut le = [0; core::mem::size_of::<$t>()];
+                    if bytes.len() != le.len() {
+                        return None;
+                    }
+                    le.copy_from_slice(bytes);
+                    Some(<$t>::from_le_bytes(le))
+                }
+            }
+        )*
+    };
+}
+
+integer!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// `usize` is encoded as a `u64`, so graphs can be moved between targets.
+impl Encode for usize {
+    fn encode(&self, out: &mut Vec<u8>) {
+        (*self as u64).encode(out);
+    }
+}
+
+impl Decode for usize {
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        u64::decode(bytes).and_then(|n| usize::try_from(n).ok())
+    }
+}
+
+/// `char` is encoded as its UTF-8 bytes.
+impl Encode for char {
+    fn encode(&self, out: &mut Vec<u8>) {
+        let mut utf8 = [0; 4];
+        out.extend_from_slice(self.encode_utf8(&mut utf8).as_bytes());
+    }
+}
+
+impl Decode for char {
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut chars = core::str::from_utf8(bytes).ok()?.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(c),
+            _ => None,
+        }
+    }
+}
+
+/// `String` is encoded as its UTF-8 bytes.
+impl Encode for String {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.as_bytes());
+    }
+}
+
+impl Decode for String {
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        core::str::from_utf8(bytes).ok().map(String::from)
+    }
+}
+
+/// `DecodeError` is raised by `BTreeGraph::from_bytes` when the bytes are not a valid
+/// encoding of a graph.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum DecodeError {
+    /// The bytes do not start with the magic number.
+    Magic,
+    /// The encoding is of a version this library can not read.
+    Version(u8),
+    /// The checksum does not match the bytes.
+    Checksum,
+    /// The bytes end before the encoding does.
+    Truncated,
+    /// A section has bytes left over after its contents.
+    SectionLength,
+    /// A vertex could not be decoded, or is not in ascending order.
+    Vertex,
+    /// An edge could not be decoded, or is listed more than once.
+    Edge,
+    /// An edge refers to a vertex index which is out of range.
+    VertexIndex,
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            DecodeError::Magic => write!(f, "Decode Error: not a graph encoding"),
+            DecodeError::Version(version) => {
+                write!(f, "Decode Error: unsupported version {}", version)
+            }
+            DecodeError::Checksum => write!(f, "Decode Error: checksum does not match"),
+            DecodeError::Truncated => write!(f, "Decode Error: encoding is truncated"),
+            DecodeError::SectionLength => write!(f, "Decode Error: section length does not match"),
+            DecodeError::Vertex => write!(f, "Decode Error: invalid vertex"),
+            DecodeError::Edge => write!(f, "Decode Error: invalid edge"),
+            DecodeError::VertexIndex => write!(f, "Decode Error: vertex index out of range"),
+        }
+    }
+}
+
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord + Encode,
+    E: Ord + Encode,
+{
+    /// Encodes the graph in the compact binary encoding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, AddEdge};
+    /// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+    /// graph.add_vertex(String::from("origin"));
+    /// graph.add_vertex(String::from("destination"));
+    /// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+    ///
+    /// let bytes = graph.to_bytes();
+    /// assert_eq!(BTreeGraph::from_bytes(&bytes), Ok(graph));
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out: Vec<u8> = Vec::new();
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+
+        let mut section: Vec<u8> = Vec::new();
+        let mut index: BTreeMap<&V, usize> = BTreeMap::new();
+        write_varint(&mut section, self.vertices.len());
+        for (i, v) in self.vertices.keys().enumerate() {
+            write_value(&mut section, v);
+            index.insert(v, i);
+        }
+        write_varint(&mut out, section.len());
+        out.append(&mut section);
+
+        write_varint(&mut section, self.edges.len());
+        for (e, (x, y)) in self.edges.iter() {
+            write_value(&mut section, e);
+            write_varint(&mut section, index[x]);
+            write_varint(&mut section, index[y]);
+        }
+        write_varint(&mut out, section.len());
+        out.append(&mut section);
+
+        let checksum = crc32(&out);
+        out.extend_from_slice(&checksum.to_le_bytes());
+        out
+    }
+}
+
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord + Clone + Decode,
+    E: Ord + Clone + Decode,
+{
+    /// Decodes a graph from the compact binary encoding, checking its version, checksum,
+    /// and that it describes a valid graph.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        if bytes.len() < MAGIC.len() || &bytes[..MAGIC.len()] != MAGIC {
+            return Err(DecodeError::Magic);
+        }
+        if bytes.len() < MAGIC.len() + 1 + 4 {
+            return Err(DecodeError::Truncated);
+        }
+        let version = bytes[MAGIC.len()];
+        if version != VERSION {
+            return Err(DecodeError::Version(version));
+        }
+        let (body, checksum) = bytes.split_at(bytes.len() - 4);
+        if crc32(body).to_le_bytes() != checksum {
+            return Err(DecodeError::Checksum);
+        }
+
+        let mut reader = Reader(&body[MAGIC.len() + 1..]);
+        let mut vertices = reader.section()?;
+        let mut edges = reader.section()?;
+        reader.finish()?;
+
+        // Vertices must be in ascending order, so they are also unique.
+        let mut graph: BTreeGraph<V, E> = BTreeGraph::new();
+        let mut index: Vec<V> = Vec::new();
+        for _ in 0..vertices.varint()? {
+            let v: V = vertices.value().ok_or(DecodeError::Vertex)?;
+            if index.last().is_some_and(|last| *last >= v) {
+                return Err(DecodeError::Vertex);
+            }
+            graph.vertices.insert(v.clone(), BTreeSet::new());
+            index.push(v);
+        }
+        vertices.finish()?;
+
+        for _ in 0..edges.varint()? {
+            let e: E = edges.value().ok_or(DecodeError::Edge)?;
+            let x = index.get(edges.varint()?).ok_or(DecodeError::VertexIndex)?;
+            let y = index.get(edges.varint()?).ok_or(DecodeError::VertexIndex)?;
+            if let Some(adjacent) = graph.vertices.get_mut(x) {
+                adjacent.insert(e.clone());
+            }
+            if graph.edges.insert(e, (x.clone(), y.clone())).is_some() {
+                return Err(DecodeError::Edge);
+            }
+        }
+        edges.finish()?;
+        Ok(graph)
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut n: usize) {
+    while n >= 0x80 {
+        out.push((n as u8 & 0x7f) | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+fn write_value<T: Encode>(out: &mut Vec<u8>, t: &T) {
+    let mut bytes: Vec<u8> = Vec::new();
+    t.encode(&mut bytes);
+    write_varint(out, bytes.len());
+    out.append(&mut bytes);
+}
+
+/// `Reader` reads from the front of the bytes left in a section.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, n: usize) -> Result<&'a [u8], DecodeError> {
+        if n > self.0.len() {
+            return Err(DecodeError::Truncated);
+        }
+        let (bytes, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn varint(&mut self) -> Result<usize, DecodeError> {
+        let mut n: usize = 0;
+        let mut shift: u32 = 0;
+        loop {
+            let byte = self.bytes(1)?[0];
+            let bits = ((byte & 0x7f) as usize)
+                .checked_shl(shift)
+                .filter(|bits| bits >> shift == (byte & 0x7f) as usize)
+                .ok_or(DecodeError::Truncated)?;
+            n |= bits;
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+            shift += 7;
+        }
+    }
+
+    fn section(&mut self) -> Result<Reader<'a>, DecodeError> {
+        let n = self.varint()?;
+        self.bytes(n).map(Reader)
+    }
+
+    /// Reads a length prefixed value, which is `None` if it can not be decoded.
+    fn value<T: Decode>(&mut self) -> Option<T> {
+        let n = self.varint().ok()?;
+        T::decode(self.bytes(n).ok()?)
+    }
+
+    fn finish(&self) -> Result<(), DecodeError> {
+        if !self.0.is_empty() {
+            return Err(DecodeError::SectionLength);
+        }
+        Ok(())
+    }
+}
+
+/// The CRC-32 (IEEE 802.3) checksum of the bytes.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc: u32 = !0;
+    for byte in bytes.iter() {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
@@ -0,0 +1,94 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+
+use crate::{AddEdgeWithVertices, BTreeGraph, Edge};
+
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    /// Builds a graph from a list of edges, creating the vertices they connect. If an edge
+    /// is given more than once, the last value is kept, but the vertices of every value are
+    /// still created, just as with `Extend`. Both maps are bulk-built from sorted input,
+    /// rather than by inserting the edges one by one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, Adjacent, Edges, Vertices};
+    /// let graph: BTreeGraph<String, usize> = BTreeGraph::from_edges(vec![
+    ///     (10, (String::from("origin"), String::from("destination"))),
+    ///     (20, (String::from("destination"), String::from("origin"))),
+    /// ]);
+    ///
+    /// assert_eq!(graph.vertices().len(), 2);
+    /// assert_eq!(graph.edges().len(), 2);
+    /// assert!(graph.adjacent(String::from("origin"), String::from("destination")).unwrap());
+    /// ```
+    pub fn from_edges<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Edge<V, E>>,
+    {
+        // Pair every vertex given with no edge, and each source with its outgoing edges, so
+        // that sorting groups the adjacency list of every vertex together, in order.
+        let mut pairs: Vec<(V, Option<E>)> = Vec::new();
+        let edges: BTreeMap<E, (V, V)> = iter
+            .into_iter()
+            .inspect(|(_, (x, y))| {
+                pairs.push((x.clone(), None));
+                pairs.push((y.clone(), None));
+            })
+            .collect();
+        for (e, (x, _)) in edges.iter() {
+            pairs.push((x.clone(), Some(e.clone())));
+        }
+        pairs.sort_unstable();
+
+        let mut adjacency: Vec<(V, BTreeSet<E>)> = Vec::new();
+        let mut pairs = pairs.into_iter().peekable();
+        while let Some((v, e)) = pairs.next() {
+            let mut outgoing: Vec<E> = e.into_iter().collect();
+            while let Some((_, e)) = pairs.next_if(|(w, _)| *w == v) {
+                outgoing.extend(e);
+            }
+            adjacency.push((v, outgoing.into_iter().collect()));
+        }
+
+        BTreeGraph {
+            vertices: adjacency.into_iter().collect(),
+            edges,
+        }
+    }
+}
+
+impl<V, E> FromIterator<Edge<V, E>> for BTreeGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Edge<V, E>>,
+    {
+        BTreeGraph::from_edges(iter)
+    }
+}
+
+/// Adds every edge, creating the vertices it connects if they do not exist. An edge which
+/// already exists has its value replaced, as with `AddEdgeWithVertices`.
+impl<V, E> Extend<Edge<V, E>> for BTreeGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = Edge<V, E>>,
+    {
+        for (e, (x, y)) in iter {
+            self.add_edge_with_vertices(x, y, e);
+        }
+    }
+}
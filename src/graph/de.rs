@@ -0,0 +1,113 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::fmt::{Debug, Display, Formatter, Result as FmtResult};
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+
+use crate::{BTreeGraph, Violation};
+
+/// `Unchecked` mirrors the serialized form of `BTreeGraph`, before its invariants are
+/// checked.
+#[derive(Deserialize)]
+#[serde(rename = "BTreeGraph")]
+struct Unchecked<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    vertices: BTreeMap<V, BTreeSet<E>>,
+    edges: BTreeMap<E, (V, V)>,
+}
+
+impl<V, E> From<Unchecked<V, E>> for BTreeGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn from(graph: Unchecked<V, E>) -> Self {
+        BTreeGraph {
+            vertices: graph.vertices,
+            edges: graph.edges,
+        }
+    }
+}
+
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    /// Deserializes a graph without checking its invariants, for input which is trusted
+    /// to have been serialized from a valid graph. Usable with
+    /// `#[serde(deserialize_with = "BTreeGraph::deserialize_unchecked")]`.
+    pub fn deserialize_unchecked<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Deserialize<'de>,
+        E: Deserialize<'de>,
+    {
+        Unchecked::deserialize(deserializer).map(BTreeGraph::from)
+    }
+}
+
+/// Deserializing a graph checks its invariants (see `BTreeGraph::validate`), raising an
+/// error which describes the first violation found if they do not hold, naming the vertex,
+/// and edge at fault.
+impl<'de, V, E> Deserialize<'de> for BTreeGraph<V, E>
+where
+    V: Ord + Debug + Deserialize<'de>,
+    E: Ord + Debug + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let graph = BTreeGraph::deserialize_unchecked(deserializer)?;
+        let violations = graph.validate();
+        if let Some(violation) = violations.first() {
+            return Err(D::Error::custom(format_args!(
+                "invalid graph: {} ({} violation(s) in total)",
+                Described(violation),
+                violations.len()
+            )));
+        }
+        Ok(graph)
+    }
+}
+
+/// `Described` formats a violation for the error raised when deserializing a graph.
+struct Described<'a, V, E>(&'a Violation<V, E>);
+
+impl<'a, V, E> Display for Described<'a, V, E>
+where
+    V: Debug,
+    E: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match self.0 {
+            Violation::MissingSource(e, v) => write!(
+                f,
+                "the source vertex {:?} of the edge {:?} does not exist",
+                v, e
+            ),
+            Violation::MissingTarget(e, v) => write!(
+                f,
+                "the target vertex {:?} of the edge {:?} does not exist",
+                v, e
+            ),
+            Violation::MissingEdge(v, e) => write!(
+                f,
+                "the vertex {:?} lists the edge {:?}, which does not exist",
+                v, e
+            ),
+            Violation::WrongSource(v, e) => write!(
+                f,
+                "the vertex {:?} lists the edge {:?}, which has another source",
+                v, e
+            ),
+            Violation::Unlisted(e) => {
+                write!(f, "the edge {:?} is not listed by its source vertex", e)
+            }
+        }
+    }
+}
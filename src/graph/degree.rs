@@ -0,0 +1,74 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+
+use crate::{BTreeGraph, IsolatedVertices, RemoveIsolatedVertices, Sinks, Sources};
+
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    /// Collects every vertex which is the destination of at least one edge.
+    fn targets(&self) -> BTreeSet<&V> {
+        self.edges.values().map(|(_, y)| y).collect()
+    }
+}
+
+impl<V, E> Sources<V> for BTreeGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn sources<'a>(&'a self) -> impl Iterator<Item = &'a V>
+    where
+        V: 'a,
+    {
+        let targets = self.targets();
+        self.vertices.keys().filter(move |v| !targets.contains(v))
+    }
+}
+
+impl<V, E> Sinks<V> for BTreeGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn sinks<'a>(&'a self) -> impl Iterator<Item = &'a V>
+    where
+        V: 'a,
+    {
+        self.vertices
+            .iter()
+            .filter(|(_, edges)| edges.is_empty())
+            .map(|(v, _)| v)
+    }
+}
+
+impl<V, E> IsolatedVertices<V> for BTreeGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn isolated_vertices<'a>(&'a self) -> impl Iterator<Item = &'a V>
+    where
+        V: 'a,
+    {
+        let targets = self.targets();
+        self.sinks().filter(move |v| !targets.contains(v))
+    }
+}
+
+impl<V, E> RemoveIsolatedVertices<V> for BTreeGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn remove_isolated_vertices(&mut self) -> BTreeSet<V> {
+        let targets: BTreeSet<&V> = self.edges.values().map(|(_, y)| y).collect();
+        let (isolated, retained): (BTreeMap<V, BTreeSet<E>>, BTreeMap<V, BTreeSet<E>>) =
+            core::mem::take(&mut self.vertices)
+                .into_iter()
+                .partition(|(v, edges)| edges.is_empty() && !targets.contains(v));
+        self.vertices = retained;
+        isolated.into_keys().collect()
+    }
+}
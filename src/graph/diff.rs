@@ -0,0 +1,246 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{ApplyDiff, BTreeGraph, Diff, Error, RemoveVertex};
+
+/// `EdgeChange` is the old, and new value of an edge whose vertices changed.
+pub type EdgeChange<V> = ((V, V), (V, V));
+
+/// `GraphDiff` is the structural difference between two graphs; applying it to the first
+/// graph yields the second.
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GraphDiff<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    /// Vertices found only in the second graph.
+    pub added_vertices: BTreeSet<V>,
+    /// Vertices found only in the first graph.
+    pub removed_vertices: BTreeSet<V>,
+    /// Edges found only in the second graph.
+    pub added_edges: BTreeMap<E, (V, V)>,
+    /// Edges found only in the first graph.
+    pub removed_edges: BTreeMap<E, (V, V)>,
+    /// Edges found in both graphs whose vertices changed, as (old, new).
+    pub changed_edges: BTreeMap<E, EdgeChange<V>>,
+}
+
+impl<V, E> GraphDiff<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    pub fn new() -> Self {
+        GraphDiff {
+            added_vertices: BTreeSet::new(),
+            removed_vertices: BTreeSet::new(),
+            added_edges: BTreeMap::new(),
+            removed_edges: BTreeMap::new(),
+            changed_edges: BTreeMap::new(),
+        }
+    }
+
+    /// Returns true if the diff makes no change.
+    pub fn is_empty(&self) -> bool {
+        self.added_vertices.is_empty()
+            && self.removed_vertices.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.changed_edges.is_empty()
+    }
+}
+
+impl<V, E> Default for GraphDiff<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `Maps` gives read access to the vertex, and edge maps of a graph, so that diffs are
+/// computed, and checked the same way for every implementation.
+pub(crate) trait Maps<V, E> {
+    fn contains_vertex(&self, v: &V) -> bool;
+    fn vertex_keys<'a>(&'a self) -> impl Iterator<Item = &'a V>
+    where
+        V: 'a;
+    fn edge_value(&self, e: &E) -> Option<&(V, V)>;
+    fn edge_entries<'a>(&'a self) -> impl Iterator<Item = (&'a E, &'a (V, V))>
+    where
+        V: 'a,
+        E: 'a;
+}
+
+impl<V, E> Maps<V, E> for BTreeGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn contains_vertex(&self, v: &V) -> bool {
+        self.vertices.contains_key(v)
+    }
+
+    fn vertex_keys<'a>(&'a self) -> impl Iterator<Item = &'a V>
+    where
+        V: 'a,
+    {
+        self.vertices.keys()
+    }
+
+    fn edge_value(&self, e: &E) -> Option<&(V, V)> {
+        self.edges.get(e)
+    }
+
+    fn edge_entries<'a>(&'a self) -> impl Iterator<Item = (&'a E, &'a (V, V))>
+    where
+        V: 'a,
+        E: 'a,
+    {
+        self.edges.iter()
+    }
+}
+
+/// Computes the diff which turns the graph `from` into the graph `to`.
+pub(crate) fn diff<V, E, G>(from: &G, to: &G) -> GraphDiff<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+    G: Maps<V, E>,
+{
+    let mut diff: GraphDiff<V, E> = GraphDiff::new();
+    for v in to.vertex_keys() {
+        if !from.contains_vertex(v) {
+            diff.added_vertices.insert(v.clone());
+        }
+    }
+    for v in from.vertex_keys() {
+        if !to.contains_vertex(v) {
+            diff.removed_vertices.insert(v.clone());
+        }
+    }
+    for (e, value) in to.edge_entries() {
+        match from.edge_value(e) {
+            None => {
+                diff.added_edges.insert(e.clone(), value.clone());
+            }
+            Some(old) if old != value => {
+                diff.changed_edges
+                    .insert(e.clone(), (old.clone(), value.clone()));
+            }
+            Some(_) => {}
+        }
+    }
+    for (e, value) in from.edge_entries() {
+        if to.edge_value(e).is_none() {
+            diff.removed_edges.insert(e.clone(), value.clone());
+        }
+    }
+    diff
+}
+
+/// Checks the diff applies to the graph, without making any change.
+pub(crate) fn check<V, E, G>(graph: &G, diff: &GraphDiff<V, E>) -> Result<(), Error>
+where
+    V: Ord,
+    E: Ord,
+    G: Maps<V, E>,
+{
+    for v in diff.removed_vertices.iter() {
+        if !graph.contains_vertex(v) {
+            return Err(Error::VertexDoesNotExist);
+        }
+    }
+    for v in diff.added_vertices.iter() {
+        if graph.contains_vertex(v) {
+            return Err(Error::VertexExists);
+        }
+    }
+    for (e, value) in diff.removed_edges.iter() {
+        if graph.edge_value(e) != Some(value) {
+            return Err(Error::EdgeDoesNotExist);
+        }
+    }
+    for (e, (old, _)) in diff.changed_edges.iter() {
+        if graph.edge_value(e) != Some(old) {
+            return Err(Error::EdgeDoesNotExist);
+        }
+    }
+    for e in diff.added_edges.keys() {
+        if graph.edge_value(e).is_some() {
+            return Err(Error::EdgeExists);
+        }
+    }
+    // The vertices of every new edge must exist once the vertices have been
+    // added, and removed.
+    let exists = |v: &V| -> bool {
+        diff.added_vertices.contains(v)
+            || (graph.contains_vertex(v) && !diff.removed_vertices.contains(v))
+    };
+    let new_edges = diff
+        .added_edges
+        .values()
+        .chain(diff.changed_edges.values().map(|(_, new)| new));
+    for (x, y) in new_edges {
+        if !exists(x) || !exists(y) {
+            return Err(Error::VertexDoesNotExist);
+        }
+    }
+    Ok(())
+}
+
+impl<V, E> Diff<V, E> for BTreeGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn diff(&self, other: &Self) -> GraphDiff<V, E> {
+        diff(self, other)
+    }
+}
+
+/// Every change is checked against the graph before any is made, so a diff which does not
+/// apply leaves the graph untouched.
+impl<V, E> ApplyDiff<V, E> for BTreeGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    type Error = Error;
+    fn apply(&mut self, diff: &GraphDiff<V, E>) -> Result<(), Self::Error> {
+        check(self, diff)?;
+
+        // Every check has passed; the source vertex of an existing edge always exists
+        // so it is safe to unwrap from here on.
+        for (e, (x, _)) in diff.removed_edges.iter() {
+            self.vertices.get_mut(x).unwrap().remove(e);
+            self.edges.remove(e);
+        }
+        for (e, ((x, _), _)) in diff.changed_edges.iter() {
+            self.vertices.get_mut(x).unwrap().remove(e);
+            self.edges.remove(e);
+        }
+        for v in diff.removed_vertices.iter() {
+            // Any edge still incident to the vertex is cascade deleted.
+            self.remove_vertex(v.clone())?;
+        }
+        for v in diff.added_vertices.iter() {
+            self.vertices.insert(v.clone(), BTreeSet::new());
+        }
+        let new_edges = diff
+            .added_edges
+            .iter()
+            .chain(diff.changed_edges.iter().map(|(e, (_, new))| (e, new)));
+        for (e, (x, y)) in new_edges {
+            self.vertices.get_mut(x).unwrap().insert(e.clone());
+            self.edges.insert(e.clone(), (x.clone(), y.clone()));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+use alloc::collections::BTreeSet;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter, Result, Write};
+
+use crate::BTreeGraph;
+
+/// Writes an adjacency list table, with a row for each vertex listing its edges, as
+/// `edge -> target`.
+pub(crate) fn write_table<'a, V, E, I>(f: &mut Formatter, rows: I) -> Result
+where
+    V: Display + 'a,
+    E: Display + 'a,
+    I: Iterator<Item = (&'a V, Vec<(&'a E, &'a V)>)>,
+{
+    let rows: Vec<(String, String)> = rows
+        .map(|(v, edges)| {
+            let mut cell = String::new();
+            for (i, (e, y)) in edges.iter().enumerate() {
+                if i > 0 {
+                    cell.push_str(", ");
+                }
+                let _ = write!(cell, "{} -> {}", e, y);
+            }
+            (v.to_string(), cell)
+        })
+        .collect();
+    let width = rows
+        .iter()
+        .map(|(v, _)| v.chars().count())
+        .fold("vertex".len(), usize::max);
+    let edges_width = rows
+        .iter()
+        .map(|(_, cell)| cell.chars().count())
+        .fold("edges".len(), usize::max);
+    writeln!(f, "{:width$} | edges", "vertex", width = width)?;
+    writeln!(
+        f,
+        "{:-<width$}-+-{:-<edges_width$}",
+        "",
+        "",
+        width = width,
+        edges_width = edges_width
+    )?;
+    for (v, cell) in rows.iter() {
+        if cell.is_empty() {
+            writeln!(f, "{:width$} |", v, width = width)?;
+        } else {
+            writeln!(f, "{:width$} | {}", v, cell, width = width)?;
+        }
+    }
+    Ok(())
+}
+
+/// Displays the graph as an adjacency list table, with a row for each vertex listing its
+/// outgoing edges, as `edge -> target`.
+///
+/// # Example
+///
+/// ```
+/// use btree_graph::{BTreeGraph, AddVertex, AddEdge};
+/// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+/// graph.add_vertex(String::from("origin"));
+/// graph.add_vertex(String::from("destination"));
+/// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+///
+/// assert_eq!(graph.to_string(), "\
+/// vertex      | edges
+/// ------------+------------------
+/// destination |
+/// origin      | 10 -> destination
+/// ");
+/// ```
+impl<V, E> Display for BTreeGraph<V, E>
+where
+    V: Ord + Display,
+    E: Ord + Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write_table(
+            f,
+            self.vertices.iter().map(|(v, edges)| {
+                let edges = edges
+                    .iter()
+                    .filter_map(|e| self.edges.get_key_value(e))
+                    .map(|(e, (_, y))| (e, y))
+                    .collect();
+                (v, edges)
+            }),
+        )
+    }
+}
+
+/// `AsciiTree` displays a graph as an ASCII tree, as returned by `BTreeGraph::ascii_tree`.
+pub struct AsciiTree<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    graph: &'g BTreeGraph<V, E>,
+}
+
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord + Display,
+    E: Ord + Display,
+{
+    /// Displays the graph as an ASCII tree, intended for forests, and DAGs. Each vertex
+    /// without incoming edges starts a tree, with the targets of its edges as children,
+    /// labelled `[edge] target`. A vertex reached again, as in a DAG, or a cycle, is
+    /// marked `(*)`, and its children are not repeated. Vertices left over, which are
+    /// only on cycles, start trees of their own.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, AddEdge};
+    /// let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+    /// graph.add_vertex(0);
+    /// graph.add_vertex(1);
+    /// graph.add_vertex(2);
+    /// graph.add_edge(0, 1, 2);
+    /// graph.add_edge(1, 2, 3);
+    /// graph.add_edge(0, 2, 4);
+    ///
+    /// assert_eq!(graph.ascii_tree().to_string(), "\
+    /// 0
+    /// |-- [2] 1
+    /// |   `-- [3] 2
+    /// `-- [4] 2 (*)
+    /// ");
+    /// ```
+    pub fn ascii_tree(&self) -> AsciiTree<'_, V, E> {
+        AsciiTree { graph: self }
+    }
+}
+
+impl<'g, V, E> Display for AsciiTree<'g, V, E>
+where
+    V: Ord + Display,
+    E: Ord + Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let graph = self.graph;
+        let targets: BTreeSet<&V> = graph.edges.values().map(|(_, y)| y).collect();
+        let roots = graph
+            .vertices
+            .keys()
+            .filter(|v| !targets.contains(v))
+            .chain(graph.vertices.keys());
+        let mut drawn: BTreeSet<&V> = BTreeSet::new();
+        for root in roots {
+            if !drawn.insert(root) {
+                continue;
+            }
+            writeln!(f, "{}", root)?;
+            // Each entry is the prefix of a level, and the edges left to draw on it.
+            let mut stack: Vec<(String, Vec<(&E, &V)>)> =
+                vec![(String::new(), children(graph, root))];
+            while let Some((prefix, edges)) = stack.last_mut() {
+                let (e, y) = match edges.pop() {
+                    Some(edge) => edge,
+                    None => {
+                        stack.pop();
+                        continue;
+                    }
+                };
+                let last = edges.is_empty();
+                let branch = if last { "`-- " } else { "|-- " };
+                if drawn.insert(y) {
+                    writeln!(f, "{}{}[{}] {}", prefix, branch, e, y)?;
+                    let prefix = alloc::format!("{}{}", prefix, if last { "    " } else { "|   " });
+                    stack.push((prefix, children(graph, y)));
+                } else {
+                    writeln!(f, "{}{}[{}] {} (*)", prefix, branch, e, y)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The edges from the vertex, and their targets, in reverse order to be popped.
+fn children<'g, V, E>(graph: &'g BTreeGraph<V, E>, v: &V) -> Vec<(&'g E, &'g V)>
+where
+    V: Ord,
+    E: Ord,
+{
+    graph
+        .vertices
+        .get(v)
+        .into_iter()
+        .flatten()
+        .rev()
+        .filter_map(|e| graph.edges.get_key_value(e))
+        .map(|(e, (_, y))| (e, y))
+        .collect()
+}
@@ -0,0 +1,493 @@
+//! Graphviz DOT export, and import.
+//!
+//! `Dot` writes a graph as DOT, using the `Display` output of each vertex as its node id,
+//! and of each edge as its `id` attribute. Labels, and other attributes can be set with
+//! closures. `parse` reads the subset of DOT made of node, and edge statements back into
+//! a `BTreeGraph<String, String>`.
+//!
+//! # Example
+//!
+//! ```
+//! use btree_graph::{BTreeGraph, AddVertex, AddEdge, Vertices};
+//! use btree_graph::dot::{parse, Dot};
+//! let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+//! graph.add_vertex(String::from("origin"));
+//! graph.add_vertex(String::from("destination"));
+//! graph.add_edge(String::from("origin"), String::from("destination"), 10);
+//!
+//! let dot = Dot::new(&graph)
+//!     .edge_attributes(|_, _| vec![(String::from("color"), String::from("red"))])
+//!     .to_string();
+//! assert_eq!(dot, r#"digraph {
+//!     "destination";
+//!     "origin";
+//!     "origin" -> "destination" [id="10", label="10", color="red"];
+//! }
+//! "#);
+//!
+//! let parsed = parse(&dot).unwrap();
+//! assert_eq!(parsed.vertices().len(), 2);
+//! ```
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter, Result, Write};
+
+use crate::{AddEdgeWithVertices, AddVertex, BTreeGraph, ParseError};
+
+type Attributes = Vec<(String, String)>;
+type Label<'g, T> = Option<Box<dyn Fn(&T) -> String + 'g>>;
+type VertexAttributes<'g, V> = Option<Box<dyn Fn(&V) -> Attributes + 'g>>;
+type EdgeAttributes<'g, V, E> = Option<Box<dyn Fn(&E, &(V, V)) -> Attributes + 'g>>;
+
+/// `Dot` writes a graph as Graphviz DOT through its `Display` implementation.
+pub struct Dot<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    graph: &'g BTreeGraph<V, E>,
+    directed: bool,
+    vertex_label: Label<'g, V>,
+    edge_label: Label<'g, E>,
+    vertex_attributes: VertexAttributes<'g, V>,
+    edge_attributes: EdgeAttributes<'g, V, E>,
+}
+
+impl<'g, V, E> Dot<'g, V, E>
+where
+    V: Ord + Display,
+    E: Ord + Display,
+{
+    /// Writes the graph as a directed graph, with each edge labelled by its id.
+    pub fn new(graph: &'g BTreeGraph<V, E>) -> Self {
+        Dot {
+            graph,
+            directed: true,
+            vertex_label: None,
+            edge_label: None,
+            vertex_attributes: None,
+            edge_attributes: None,
+        }
+    }
+
+    /// Writes the graph as an undirected graph instead.
+    pub fn undirected(mut self) -> Self {
+        self.directed = false;
+        self
+    }
+
+    /// Labels each vertex, which is otherwise labelled by its id.
+    pub fn vertex_label<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&V) -> String + 'g,
+    {
+        self.vertex_label = Some(Box::new(f));
+        self
+    }
+
+    /// Labels each edge, which is otherwise labelled by its id.
+    pub fn edge_label<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&E) -> String + 'g,
+    {
+        self.edge_label = Some(Box::new(f));
+        self
+    }
+
+    /// Adds attributes, as name, value pairs, to each vertex.
+    pub fn vertex_attributes<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&V) -> Attributes + 'g,
+    {
+        self.vertex_attributes = Some(Box::new(f));
+        self
+    }
+
+    /// Adds attributes, as name, value pairs, to each edge.
+    pub fn edge_attributes<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&E, &(V, V)) -> Attributes + 'g,
+    {
+        self.edge_attributes = Some(Box::new(f));
+        self
+    }
+}
+
+impl<'g, V, E> Display for Dot<'g, V, E>
+where
+    V: Ord + Display,
+    E: Ord + Display,
+{
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let (keyword, operator) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        writeln!(f, "{} {{", keyword)?;
+        for v in self.graph.vertices.keys() {
+            f.write_str("    ")?;
+            write_quoted(f, v)?;
+            let mut attributes: Attributes = Vec::new();
+            if let Some(label) = self.vertex_label.as_ref() {
+                attributes.push((String::from("label"), label(v)));
+            }
+            if let Some(extra) = self.vertex_attributes.as_ref() {
+                attributes.extend(extra(v));
+            }
+            write_attributes(f, &attributes)?;
+            f.write_str(";\n")?;
+        }
+        for (e, value) in self.graph.edges.iter() {
+            f.write_str("    ")?;
+            write_quoted(f, &value.0)?;
+            write!(f, " {} ", operator)?;
+            write_quoted(f, &value.1)?;
+            f.write_str(" [id=")?;
+            write_quoted(f, e)?;
+            f.write_str(", label=")?;
+            match self.edge_label.as_ref() {
+                Some(label) => write_quoted(f, &label(e))?,
+                None => write_quoted(f, e)?,
+            }
+            if let Some(extra) = self.edge_attributes.as_ref() {
+                for (name, value) in extra(e, value).iter() {
+                    write!(f, ", {}=", name)?;
+                    write_quoted(f, value)?;
+                }
+            }
+            f.write_str("];\n")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// `Escaped` escapes the quotes, and backslashes written through it, so the text can be
+/// placed in a quoted DOT id.
+struct Escaped<'a, 'b>(&'a mut Formatter<'b>);
+
+impl<'a, 'b> Write for Escaped<'a, 'b> {
+    fn write_str(&mut self, s: &str) -> Result {
+        for c in s.chars() {
+            if c == '"' || c == '\\' {
+                self.0.write_char('\\')?;
+            }
+            self.0.write_char(c)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_quoted<T: Display + ?Sized>(f: &mut Formatter, t: &T) -> Result {
+    f.write_char('"')?;
+    write!(Escaped(f), "{}", t)?;
+    f.write_char('"')
+}
+
+fn write_attributes(f: &mut Formatter, attributes: &[(String, String)]) -> Result {
+    if attributes.is_empty() {
+        return Ok(());
+    }
+    f.write_str(" [")?;
+    for (i, (name, value)) in attributes.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{}=", name)?;
+        write_quoted(f, value)?;
+    }
+    f.write_char(']')
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+enum Token {
+    /// An id, and whether it was quoted; unquoted ids may be keywords.
+    Id(String, bool),
+    /// An edge operator, and whether it is directed.
+    Edge(bool),
+    LeftBrace,
+    RightBrace,
+    LeftBracket,
+    RightBracket,
+    Equals,
+    Separator,
+}
+
+impl Token {
+    fn is_keyword(&self, keyword: &str) -> bool {
+        match self {
+            Token::Id(id, false) => id.eq_ignore_ascii_case(keyword),
+            _ => false,
+        }
+    }
+}
+
+fn error(line: usize, message: &'static str) -> ParseError {
+    ParseError { line, message }
+}
+
+/// Splits the text into tokens, each with the line it starts on, skipping comments.
+fn tokenize(input: &str) -> core::result::Result<Vec<(Token, usize)>, ParseError> {
+    let mut tokens: Vec<(Token, usize)> = Vec::new();
+    let mut chars = input.chars().peekable();
+    let mut line: usize = 1;
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() => (),
+            '{' => tokens.push((Token::LeftBrace, line)),
+            '}' => tokens.push((Token::RightBrace, line)),
+            '[' => tokens.push((Token::LeftBracket, line)),
+            ']' => tokens.push((Token::RightBracket, line)),
+            '=' => tokens.push((Token::Equals, line)),
+            ';' | ',' => tokens.push((Token::Separator, line)),
+            '#' => while chars.next_if(|c| *c != '\n').is_some() {},
+            '/' if chars.peek() == Some(&'/') => while chars.next_if(|c| *c != '\n').is_some() {},
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let start = line;
+                let mut previous = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if previous == '*' => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            previous = c;
+                        }
+                        None => return Err(error(start, "unterminated comment")),
+                    }
+                }
+            }
+            '-' if chars.peek() == Some(&'>') => {
+                chars.next();
+                tokens.push((Token::Edge(true), line));
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                chars.next();
+                tokens.push((Token::Edge(false), line));
+            }
+            '"' => {
+                let start = line;
+                let mut id = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c) if c == '"' || c == '\\' => id.push(c),
+                            // A backslash followed by a newline continues the line.
+                            Some('\n') => line += 1,
+                            Some(c) => {
+                                id.push('\\');
+                                id.push(c);
+                            }
+                            None => return Err(error(start, "unterminated string")),
+                        },
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            id.push(c);
+                        }
+                        None => return Err(error(start, "unterminated string")),
+                    }
+                }
+                tokens.push((Token::Id(id, true), start));
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let mut id = String::new();
+                id.push(c);
+                while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_' || *c == '.')
+                {
+                    id.push(c);
+                }
+                tokens.push((Token::Id(id, false), line));
+            }
+            '<' => return Err(error(line, "HTML strings are not supported")),
+            ':' => return Err(error(line, "ports are not supported")),
+            _ => return Err(error(line, "unexpected character")),
+        }
+    }
+    Ok(tokens)
+}
+
+/// `Parser` walks the tokens of a DOT document.
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    /// The line of the next token, or of the last token at the end of input.
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .or_else(|| self.tokens.last())
+            .map_or(1, |(_, line)| *line)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .map(|(token, _)| token.clone());
+        self.position += 1;
+        token
+    }
+
+    fn expect(
+        &mut self,
+        expected: Token,
+        message: &'static str,
+    ) -> core::result::Result<(), ParseError> {
+        let line = self.line();
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            _ => Err(error(line, message)),
+        }
+    }
+
+    fn id(&mut self, message: &'static str) -> core::result::Result<String, ParseError> {
+        let line = self.line();
+        match self.next() {
+            Some(Token::Id(id, _)) => Ok(id),
+            _ => Err(error(line, message)),
+        }
+    }
+
+    /// Reads any attribute lists following a statement.
+    fn attributes(&mut self) -> core::result::Result<Attributes, ParseError> {
+        let mut attributes: Attributes = Vec::new();
+        while self.peek() == Some(&Token::LeftBracket) {
+            self.next();
+            loop {
+                match self.peek() {
+                    Some(Token::RightBracket) => {
+                        self.next();
+                        break;
+                    }
+                    Some(Token::Separator) => {
+                        self.next();
+                    }
+                    _ => {
+                        let name = self.id("expected an attribute name")?;
+                        self.expect(Token::Equals, "expected '=' after an attribute name")?;
+                        let value = self.id("expected an attribute value")?;
+                        attributes.push((name, value));
+                    }
+                }
+            }
+        }
+        Ok(attributes)
+    }
+}
+
+/// Reads the node, and edge statements of a DOT graph into a graph. Every edge statement
+/// must have an `id` attribute, which becomes the edge; vertices are created for the
+/// nodes the edges connect. Other attributes, and attribute statements are ignored.
+/// Edges of an undirected graph are read from left to right. Subgraphs, ports, HTML
+/// strings, and chained edge statements are not supported.
+///
+/// # Example
+///
+/// ```
+/// use btree_graph::{GetEdgeValue, Vertices};
+/// use btree_graph::dot::parse;
+///
+/// let graph = parse(r#"
+///     digraph routes {
+///         origin [color=green];
+///         origin -> destination [id=10];
+///     }
+/// "#).unwrap();
+///
+/// assert_eq!(graph.vertices().len(), 2);
+/// assert_eq!(
+///     graph.get_edge_value(String::from("10")),
+///     Some(&(String::from("origin"), String::from("destination")))
+/// );
+/// ```
+pub fn parse(input: &str) -> core::result::Result<BTreeGraph<String, String>, ParseError> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        position: 0,
+    };
+    let mut graph: BTreeGraph<String, String> = BTreeGraph::new();
+
+    if parser
+        .peek()
+        .is_some_and(|token| token.is_keyword("strict"))
+    {
+        parser.next();
+    }
+    let directed = match parser.peek() {
+        Some(token) if token.is_keyword("digraph") => true,
+        Some(token) if token.is_keyword("graph") => false,
+        _ => return Err(error(parser.line(), "expected 'graph', or 'digraph'")),
+    };
+    parser.next();
+    if let Some(Token::Id(_, _)) = parser.peek() {
+        parser.next();
+    }
+    parser.expect(Token::LeftBrace, "expected '{'")?;
+
+    loop {
+        let line = parser.line();
+        match parser.next() {
+            Some(Token::RightBrace) => break,
+            Some(Token::Separator) => (),
+            Some(token) if token.is_keyword("subgraph") => {
+                return Err(error(line, "subgraphs are not supported"))
+            }
+            Some(token)
+                if token.is_keyword("graph")
+                    || token.is_keyword("node")
+                    || token.is_keyword("edge") =>
+            {
+                parser.attributes()?;
+            }
+            Some(Token::Id(x, _)) => match parser.peek() {
+                Some(Token::Equals) => {
+                    parser.next();
+                    parser.id("expected a value after '='")?;
+                }
+                Some(Token::Edge(edge_directed)) => {
+                    if *edge_directed != directed {
+                        return Err(error(line, "edge operator does not match the graph"));
+                    }
+                    parser.next();
+                    let y = parser.id("expected a node id after the edge operator")?;
+                    if let Some(Token::Edge(_)) = parser.peek() {
+                        return Err(error(line, "chained edge statements are not supported"));
+                    }
+                    let attributes = parser.attributes()?;
+                    let e = match attributes.into_iter().find(|(name, _)| name == "id") {
+                        Some((_, e)) => e,
+                        None => return Err(error(line, "edge statement has no id")),
+                    };
+                    if graph.edges.contains_key(&e) {
+                        return Err(error(line, "edge id is used more than once"));
+                    }
+                    graph.add_edge_with_vertices(x, y, e);
+                }
+                _ => {
+                    parser.attributes()?;
+                    graph.add_vertex(x);
+                }
+            },
+            Some(_) => return Err(error(line, "expected a statement")),
+            None => return Err(error(line, "expected '}'")),
+        }
+    }
+    if parser.peek().is_some() {
+        return Err(error(parser.line(), "unexpected text after the graph"));
+    }
+    Ok(graph)
+}
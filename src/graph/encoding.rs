@@ -0,0 +1,100 @@
+#[cfg(any(feature = "serde_json", feature = "serde_yaml"))]
+use alloc::string::String;
+#[cfg(feature = "serde_cbor")]
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::BTreeGraph;
+
+/// Encodings of the graph in its serde representation.
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord + Serialize,
+    E: Ord + Serialize,
+{
+    /// Encodes the graph as JSON.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, AddEdge};
+    /// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+    /// graph.add_vertex(String::from("origin"));
+    /// graph.add_vertex(String::from("destination"));
+    /// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+    ///
+    /// let json = graph.to_json().unwrap();
+    /// assert_eq!(BTreeGraph::from_json(&json).unwrap(), graph);
+    /// ```
+    #[cfg(feature = "serde_json")]
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Encodes the graph as YAML.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, AddEdge};
+    /// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+    /// graph.add_vertex(String::from("origin"));
+    /// graph.add_vertex(String::from("destination"));
+    /// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+    ///
+    /// let yaml = graph.to_yaml().unwrap();
+    /// assert_eq!(BTreeGraph::from_yaml(&yaml).unwrap(), graph);
+    /// ```
+    #[cfg(feature = "serde_yaml")]
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(self)
+    }
+
+    /// Encodes the graph as CBOR.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, AddEdge};
+    /// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+    /// graph.add_vertex(String::from("origin"));
+    /// graph.add_vertex(String::from("destination"));
+    /// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+    ///
+    /// let cbor = graph.to_cbor().unwrap();
+    /// assert_eq!(BTreeGraph::from_cbor(&cbor).unwrap(), graph);
+    /// ```
+    #[cfg(feature = "serde_cbor")]
+    pub fn to_cbor(&self) -> Result<Vec<u8>, serde_cbor::Error> {
+        serde_cbor::to_vec(self)
+    }
+}
+
+/// Decodings of the graph from its serde representation. Decoding checks the invariants of
+/// the graph, as with deserializing it.
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord + Debug + DeserializeOwned,
+    E: Ord + Debug + DeserializeOwned,
+{
+    /// Decodes a graph from JSON.
+    #[cfg(feature = "serde_json")]
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Decodes a graph from YAML.
+    #[cfg(feature = "serde_yaml")]
+    pub fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+
+    /// Decodes a graph from CBOR.
+    #[cfg(feature = "serde_cbor")]
+    pub fn from_cbor(cbor: &[u8]) -> Result<Self, serde_cbor::Error> {
+        serde_cbor::from_slice(cbor)
+    }
+}
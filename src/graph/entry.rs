@@ -0,0 +1,177 @@
+use alloc::collections::BTreeSet;
+
+use crate::{AddEdge, AddEdgeWithVertices, BTreeGraph, Error, RemoveVertex, RemovedEdges};
+
+/// `Entry` is a view into a single vertex of a graph, which may either be there, or not.
+/// See `BTreeGraph::entry`.
+///
+/// Unlike a `btree_map::Entry`, an entry holds the whole graph rather than a position in
+/// its vertex map, as adding an edge may look up, add, or update other vertices too. Each
+/// operation therefore looks the vertex up again.
+pub enum Entry<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    Occupied(OccupiedEntry<'g, V, E>),
+    Vacant(VacantEntry<'g, V, E>),
+}
+
+/// `OccupiedEntry` is a vertex which is there, so adding an edge from it only fails if
+/// the other vertex is not.
+pub struct OccupiedEntry<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    graph: &'g mut BTreeGraph<V, E>,
+    key: V,
+}
+
+/// `VacantEntry` is a vertex which is not there.
+pub struct VacantEntry<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    graph: &'g mut BTreeGraph<V, E>,
+    key: V,
+}
+
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    /// Returns the entry of the vertex v. Whether the vertex is there is decided up front,
+    /// but the vertex is looked up again by every operation on the entry, see `Entry`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, Connections, Entry};
+    /// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+    ///
+    /// let mut origin = graph.entry(String::from("origin")).or_insert();
+    /// origin.add_edge_with_vertex(String::from("destination"), 10);
+    /// origin.add_edge(String::from("origin"), 20).unwrap();
+    /// assert_eq!(origin.get().len(), 2);
+    ///
+    /// match graph.entry(String::from("destination")) {
+    ///     Entry::Occupied(_) => (),
+    ///     Entry::Vacant(_) => panic!("destination was added by the edge"),
+    /// }
+    /// assert_eq!(graph.connections(String::from("origin")).unwrap().len(), 2);
+    /// ```
+    pub fn entry(&mut self, v: V) -> Entry<'_, V, E> {
+        if self.vertices.contains_key(&v) {
+            Entry::Occupied(OccupiedEntry {
+                graph: self,
+                key: v,
+            })
+        } else {
+            Entry::Vacant(VacantEntry {
+                graph: self,
+                key: v,
+            })
+        }
+    }
+}
+
+impl<'g, V, E> Entry<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    pub fn key(&self) -> &V {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+}
+
+impl<'g, V, E> Entry<'g, V, E>
+where
+    V: Ord + Clone,
+    E: Ord,
+{
+    /// Adds the vertex if it is not there, returning its occupied entry.
+    pub fn or_insert(self) -> OccupiedEntry<'g, V, E> {
+        match self {
+            Entry::Occupied(entry) => entry,
+            Entry::Vacant(entry) => entry.insert(),
+        }
+    }
+}
+
+impl<'g, V, E> OccupiedEntry<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    pub fn key(&self) -> &V {
+        &self.key
+    }
+
+    /// Returns the adjacency list of the vertex.
+    pub fn get(&self) -> &BTreeSet<E> {
+        // The entry is occupied, so it is safe to unwrap.
+        self.graph.vertices.get(&self.key).unwrap()
+    }
+}
+
+impl<'g, V, E> OccupiedEntry<'g, V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    /// Adds an edge from the vertex to the vertex y, as with `AddEdge`. An error is raised
+    /// if y does not exist.
+    pub fn add_edge(&mut self, y: V, e: E) -> Result<Option<(V, V)>, Error> {
+        self.graph.add_edge(self.key.clone(), y, e)
+    }
+
+    /// Adds an edge from the vertex to the vertex y, first adding y if it is not there, as
+    /// with `AddEdgeWithVertices`.
+    pub fn add_edge_with_vertex(&mut self, y: V, e: E) -> Option<(V, V)> {
+        self.graph.add_edge_with_vertices(self.key.clone(), y, e)
+    }
+
+    /// Removes the vertex, as with `RemoveVertex`.
+    pub fn remove(self) -> RemovedEdges<V, E> {
+        // The entry is occupied, so it is safe to unwrap.
+        self.graph.remove_vertex(self.key).unwrap()
+    }
+}
+
+impl<'g, V, E> VacantEntry<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    pub fn key(&self) -> &V {
+        &self.key
+    }
+
+    pub fn into_key(self) -> V {
+        self.key
+    }
+}
+
+impl<'g, V, E> VacantEntry<'g, V, E>
+where
+    V: Ord + Clone,
+    E: Ord,
+{
+    /// Adds the vertex, with no edges, returning its occupied entry.
+    pub fn insert(self) -> OccupiedEntry<'g, V, E> {
+        self.graph
+            .vertices
+            .insert(self.key.clone(), BTreeSet::new());
+        OccupiedEntry {
+            graph: self.graph,
+            key: self.key,
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::{Adjacent, BTreeGraph, Connections, Edges, Error, GetEdgeValue, Vertices};
+
+/// `FrozenGraph` is an immutable snapshot of a `BTreeGraph` in compressed sparse row form,
+/// for graphs which are built once, and read many times. Vertices are numbered densely in
+/// ascending order, and looked up by binary search rather than through a tree. It
+/// implements the read traits, so algorithms written against them work on either graph.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct FrozenGraph<V, E> {
+    /// The vertices, in ascending order; the index of a vertex is its position.
+    vertices: Vec<V>,
+    /// The edges, and their values, in ascending order.
+    edges: Vec<(E, (V, V))>,
+    /// The edges from the vertex at index i are at `offsets[i]..offsets[i + 1]` of
+    /// `targets`, and `slots`.
+    offsets: Vec<usize>,
+    /// The index of the target of each edge.
+    targets: Vec<usize>,
+    /// The position of each edge in `edges`.
+    slots: Vec<usize>,
+}
+
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    /// Copies the graph into a `FrozenGraph`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, AddEdge, Adjacent};
+    /// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+    /// graph.add_vertex(String::from("origin"));
+    /// graph.add_vertex(String::from("destination"));
+    /// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+    ///
+    /// let frozen = graph.freeze();
+    /// assert!(frozen.adjacent(String::from("origin"), String::from("destination")).unwrap());
+    /// assert_eq!(frozen.index_of(&String::from("origin")), Some(1));
+    /// ```
+    pub fn freeze(&self) -> FrozenGraph<V, E> {
+        let vertices: Vec<V> = self.vertices.keys().cloned().collect();
+        let edges: Vec<(E, (V, V))> = self
+            .edges
+            .iter()
+            .map(|(e, value)| (e.clone(), value.clone()))
+            .collect();
+        let mut offsets: Vec<usize> = Vec::with_capacity(vertices.len() + 1);
+        let mut targets: Vec<usize> = Vec::with_capacity(edges.len());
+        let mut slots: Vec<usize> = Vec::with_capacity(edges.len());
+        offsets.push(0);
+        for adjacent in self.vertices.values() {
+            for e in adjacent.iter() {
+                // Edges in an adjacency list and their targets always exist, so it is safe to
+                // unwrap here.
+                let slot = edges.binary_search_by(|(key, _)| key.cmp(e)).unwrap();
+                let y = &edges[slot].1 .1;
+                targets.push(vertices.binary_search(y).unwrap());
+                slots.push(slot);
+            }
+            offsets.push(slots.len());
+        }
+        FrozenGraph {
+            vertices,
+            edges,
+            offsets,
+            targets,
+            slots,
+        }
+    }
+}
+
+impl<V, E> FrozenGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    /// Returns the number of vertices.
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// Returns the number of edges.
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Returns the index of the vertex, if it exists.
+    pub fn index_of(&self, v: &V) -> Option<usize> {
+        self.vertices.binary_search(v).ok()
+    }
+
+    /// Returns the vertex at the index, if there is one.
+    pub fn vertex(&self, i: usize) -> Option<&V> {
+        self.vertices.get(i)
+    }
+
+    /// Returns the indices of the targets of the edges from the vertex at the index, in
+    /// ascending order of edge, or an empty slice if there is no such vertex.
+    pub fn targets(&self, i: usize) -> &[usize] {
+        match (self.offsets.get(i), self.offsets.get(i + 1)) {
+            (Some(start), Some(end)) => &self.targets[*start..*end],
+            _ => &[],
+        }
+    }
+
+    /// Returns the edges from the vertex at the index, in ascending order, along with the
+    /// index of the target of each.
+    pub fn edges_from(&self, i: usize) -> impl Iterator<Item = (&E, usize)> + '_ {
+        let range = match (self.offsets.get(i), self.offsets.get(i + 1)) {
+            (Some(start), Some(end)) => *start..*end,
+            _ => 0..0,
+        };
+        range.map(move |slot| (&self.edges[self.slots[slot]].0, self.targets[slot]))
+    }
+}
+
+impl<V, E> Vertices<V> for FrozenGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn vertices(&self) -> BTreeSet<&V> {
+        self.vertices.iter().collect()
+    }
+}
+
+impl<V, E> Edges<E> for FrozenGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn edges(&self) -> BTreeSet<&E> {
+        self.edges.iter().map(|(e, _)| e).collect()
+    }
+}
+
+impl<V, E> GetEdgeValue<V, E> for FrozenGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn get_edge_value(&self, e: E) -> Option<&(V, V)> {
+        self.edges
+            .binary_search_by(|(key, _)| key.cmp(&e))
+            .ok()
+            .map(|slot| &self.edges[slot].1)
+    }
+}
+
+impl<V, E> Adjacent<V> for FrozenGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    type Error = Error;
+    fn adjacent(&self, x: V, y: V) -> Result<bool, Self::Error> {
+        match (self.index_of(&x), self.index_of(&y)) {
+            (Some(x), Some(y)) => Ok(self.targets(x).contains(&y)),
+            _ => Err(Error::VertexDoesNotExist),
+        }
+    }
+}
+
+impl<V, E> Connections<V> for FrozenGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    type Error = Error;
+    fn connections(&self, v: V) -> Result<BTreeSet<&V>, Self::Error> {
+        match self.index_of(&v) {
+            Some(i) => Ok(self.targets(i).iter().map(|y| &self.vertices[*y]).collect()),
+            None => Err(Error::VertexDoesNotExist),
+        }
+    }
+}
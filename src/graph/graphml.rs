@@ -0,0 +1,575 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Display;
+use core::str::FromStr;
+
+use crate::{BTreeGraph, ParseError};
+
+type Attributes = Vec<(String, String)>;
+
+/// GraphML export, as read by tools such as Gephi, and yEd. Vertices are written as nodes,
+/// and edges as edges, using the `Display` output of each as its id.
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord + Display,
+    E: Ord + Display,
+{
+    /// Writes the graph as a directed GraphML document.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, AddEdge};
+    /// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+    /// graph.add_vertex(String::from("origin"));
+    /// graph.add_vertex(String::from("destination"));
+    /// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+    ///
+    /// let graphml = graph.to_graphml();
+    /// assert_eq!(BTreeGraph::from_graphml(&graphml), Ok(graph));
+    /// ```
+    pub fn to_graphml(&self) -> String {
+        self.to_graphml_with(|_| Vec::new(), |_, _| Vec::new())
+    }
+
+    /// Writes the graph as a directed GraphML document, with the data attributes, as
+    /// name, value pairs, returned for each vertex, and edge. A string key is declared
+    /// for every attribute name used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, AddEdge};
+    /// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+    /// graph.add_vertex(String::from("origin"));
+    /// graph.add_vertex(String::from("destination"));
+    /// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+    ///
+    /// let graphml = graph.to_graphml_with(
+    ///     |v| vec![(String::from("length"), v.len().to_string())],
+    ///     |_, _| Vec::new(),
+    /// );
+    /// assert!(graphml.contains(r#"<data key="d0">6</data>"#));
+    /// ```
+    pub fn to_graphml_with<FV, FE>(&self, vertex_data: FV, edge_data: FE) -> String
+    where
+        FV: Fn(&V) -> Attributes,
+        FE: Fn(&E, &(V, V)) -> Attributes,
+    {
+        let nodes: Vec<(String, Attributes)> = self
+            .vertices
+            .keys()
+            .map(|v| {
+                (
+                    alloc::format!("id=\"{}\"", escape(&v.to_string())),
+                    vertex_data(v),
+                )
+            })
+            .collect();
+        let edges: Vec<(String, Attributes)> = self
+            .edges
+            .iter()
+            .map(|(e, value)| {
+                let attributes = alloc::format!(
+                    "id=\"{}\" source=\"{}\" target=\"{}\"",
+                    escape(&e.to_string()),
+                    escape(&value.0.to_string()),
+                    escape(&value.1.to_string())
+                );
+                (attributes, edge_data(e, value))
+            })
+            .collect();
+
+        // Keys are numbered in the order their names are first used.
+        let mut keys: Vec<(&str, &str)> = Vec::new();
+        for (domain, elements) in [("node", &nodes), ("edge", &edges)].iter() {
+            for (_, data) in elements.iter() {
+                for (name, _) in data.iter() {
+                    if !keys.contains(&(domain, name)) {
+                        keys.push((domain, name));
+                    }
+                }
+            }
+        }
+
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        for (i, (domain, name)) in keys.iter().enumerate() {
+            out.push_str(&alloc::format!(
+                "  <key id=\"d{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"string\"/>\n",
+                i,
+                domain,
+                escape(name)
+            ));
+        }
+        out.push_str("  <graph edgedefault=\"directed\">\n");
+        for (domain, elements) in [("node", &nodes), ("edge", &edges)].iter() {
+            for (attributes, data) in elements.iter() {
+                out.push_str(&alloc::format!("    <{} {}", domain, attributes));
+                if data.is_empty() {
+                    out.push_str("/>\n");
+                    continue;
+                }
+                out.push_str(">\n");
+                for (name, value) in data.iter() {
+                    let key = keys.iter().position(|key| *key == (*domain, name));
+                    out.push_str(&alloc::format!(
+                        "      <data key=\"d{}\">{}</data>\n",
+                        key.unwrap_or_default(),
+                        escape(value)
+                    ));
+                }
+                out.push_str(&alloc::format!("    </{}>\n", domain));
+            }
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+}
+
+/// GraphML import. Node ids are parsed as vertices, and edge ids as edges, using `FromStr`.
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord + FromStr,
+    E: Ord + Clone + FromStr,
+{
+    /// Reads the first graph of a GraphML document, ignoring any data attributes. Every
+    /// edge must have an id. Edges of an undirected graph are read from source to target.
+    /// Nested graphs, and hyperedges are not supported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, GetEdgeValue};
+    /// let graph: BTreeGraph<String, usize> = BTreeGraph::from_graphml(r#"
+    ///     <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+    ///       <graph edgedefault="directed">
+    ///         <node id="origin"/>
+    ///         <node id="destination"/>
+    ///         <edge id="10" source="origin" target="destination"/>
+    ///       </graph>
+    ///     </graphml>
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(
+    ///     graph.get_edge_value(10),
+    ///     Some(&(String::from("origin"), String::from("destination")))
+    /// );
+    /// ```
+    pub fn from_graphml(input: &str) -> Result<Self, ParseError> {
+        BTreeGraph::from_graphml_with(input, |_, _| (), |_, _| ())
+    }
+
+    /// Reads the first graph of a GraphML document, as `from_graphml`, passing the data
+    /// attributes of each vertex, and edge, as name, value pairs, to the given closures.
+    /// Attributes are named by the `attr.name` of their key, and include key defaults.
+    pub fn from_graphml_with<FV, FE>(
+        input: &str,
+        mut vertex_data: FV,
+        mut edge_data: FE,
+    ) -> Result<Self, ParseError>
+    where
+        FV: FnMut(&V, &[(String, String)]),
+        FE: FnMut(&E, &[(String, String)]),
+    {
+        let document = Document::read(input)?;
+        let mut graph: BTreeGraph<V, E> = BTreeGraph::new();
+        for node in document.nodes.iter() {
+            let v = parse::<V>(node, "id", "node id is not a valid vertex")?;
+            vertex_data(&v, &document.data(node, "node")?);
+            if graph.vertices.insert(v, BTreeSet::new()).is_some() {
+                return Err(error(node.line, "node id is used more than once"));
+            }
+        }
+        for edge in document.edges.iter() {
+            let e = parse::<E>(edge, "id", "edge id is not a valid edge")?;
+            let x = parse::<V>(edge, "source", "edge source is not a valid vertex")?;
+            let y = parse::<V>(edge, "target", "edge target is not a valid vertex")?;
+            if !graph.vertices.contains_key(&y) {
+                return Err(error(edge.line, "edge target is not a declared node"));
+            }
+            match graph.vertices.get_mut(&x) {
+                Some(edges) => {
+                    edges.insert(e.clone());
+                }
+                None => return Err(error(edge.line, "edge source is not a declared node")),
+            }
+            edge_data(&e, &document.data(edge, "edge")?);
+            if graph.edges.insert(e, (x, y)).is_some() {
+                return Err(error(edge.line, "edge id is used more than once"));
+            }
+        }
+        Ok(graph)
+    }
+}
+
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(s: &str, line: usize) -> Result<String, ParseError> {
+    let mut unescaped = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+        let end = match rest.find(';') {
+            Some(end) => end,
+            None => return Err(error(line, "unterminated entity")),
+        };
+        let c = match &rest[..end] {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            entity if entity.starts_with("#x") => u32::from_str_radix(&entity[2..], 16)
+                .ok()
+                .and_then(core::char::from_u32),
+            entity if entity.starts_with('#') => {
+                entity[1..].parse().ok().and_then(core::char::from_u32)
+            }
+            _ => return Err(error(line, "unknown entity")),
+        };
+        match c {
+            Some(c) => unescaped.push(c),
+            None => return Err(error(line, "invalid character reference")),
+        }
+        rest = &rest[end + 1..];
+    }
+    unescaped.push_str(rest);
+    Ok(unescaped)
+}
+
+fn error(line: usize, message: &'static str) -> ParseError {
+    ParseError { line, message }
+}
+
+fn parse<T: FromStr>(
+    element: &Element,
+    name: &str,
+    message: &'static str,
+) -> Result<T, ParseError> {
+    element
+        .attribute(name)
+        .ok_or_else(|| error(element.line, "missing id, source, or target attribute"))?
+        .parse()
+        .map_err(|_| error(element.line, message))
+}
+
+enum Event {
+    /// A start tag, its attributes, and whether it is also the end tag.
+    Start(String, Attributes, bool),
+    End(String),
+    Text(String),
+}
+
+/// `Reader` splits an XML document into tags, and text, each with the line it starts on,
+/// skipping declarations, processing instructions, and comments.
+struct Reader<'a> {
+    input: &'a str,
+    position: usize,
+    line: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn advance(&mut self, n: usize) -> &'a str {
+        let consumed = &self.input[self.position..self.position + n];
+        self.line += consumed.matches('\n').count();
+        self.position += n;
+        consumed
+    }
+
+    /// Consumes the text up to, and including the terminator, returning the text before it.
+    fn until(&mut self, terminator: &str, message: &'static str) -> Result<&'a str, ParseError> {
+        let line = self.line;
+        match self.rest().find(terminator) {
+            Some(n) => {
+                let consumed = self.advance(n);
+                self.advance(terminator.len());
+                Ok(consumed)
+            }
+            None => Err(error(line, message)),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.advance(rest.len() - rest.trim_start().len());
+    }
+
+    fn name(&mut self) -> Result<&'a str, ParseError> {
+        let n = self
+            .rest()
+            .find(|c: char| c.is_whitespace() || c == '/' || c == '>' || c == '=')
+            .unwrap_or_else(|| self.rest().len());
+        if n == 0 {
+            return Err(error(self.line, "expected a name"));
+        }
+        Ok(self.advance(n))
+    }
+
+    fn next(&mut self) -> Result<Option<(Event, usize)>, ParseError> {
+        loop {
+            let rest = self.rest();
+            let line = self.line;
+            if rest.is_empty() {
+                return Ok(None);
+            } else if rest.starts_with("<?") {
+                self.until("?>", "unterminated processing instruction")?;
+            } else if rest.starts_with("<!--") {
+                self.until("-->", "unterminated comment")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.advance("<![CDATA[".len());
+                let text = self.until("]]>", "unterminated CDATA section")?;
+                return Ok(Some((Event::Text(String::from(text)), line)));
+            } else if rest.starts_with("<!") {
+                self.until(">", "unterminated declaration")?;
+            } else if rest.starts_with("</") {
+                self.advance(2);
+                let name = self.until(">", "unterminated end tag")?;
+                return Ok(Some((Event::End(String::from(name.trim_end())), line)));
+            } else if rest.starts_with('<') {
+                self.advance(1);
+                return self.start(line).map(Some);
+            } else {
+                let n = rest.find('<').unwrap_or(rest.len());
+                let text = unescape(self.advance(n), line)?;
+                return Ok(Some((Event::Text(text), line)));
+            }
+        }
+    }
+
+    fn start(&mut self, line: usize) -> Result<(Event, usize), ParseError> {
+        let name = String::from(self.name()?);
+        let mut attributes: Attributes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.advance(2);
+                return Ok((Event::Start(name, attributes, true), line));
+            } else if rest.starts_with('>') {
+                self.advance(1);
+                return Ok((Event::Start(name, attributes, false), line));
+            } else if rest.is_empty() {
+                return Err(error(line, "unterminated start tag"));
+            }
+            let attribute = String::from(self.name()?);
+            self.skip_whitespace();
+            if !self.rest().starts_with('=') {
+                return Err(error(self.line, "expected '=' after an attribute name"));
+            }
+            self.advance(1);
+            self.skip_whitespace();
+            let quote = match self.rest().chars().next() {
+                Some(quote) if quote == '"' || quote == '\'' => quote,
+                _ => return Err(error(self.line, "expected a quoted attribute value")),
+            };
+            self.advance(1);
+            let value_line = self.line;
+            let value = match quote {
+                '"' => self.until("\"", "unterminated attribute value")?,
+                _ => self.until("'", "unterminated attribute value")?,
+            };
+            attributes.push((attribute, unescape(value, value_line)?));
+        }
+    }
+}
+
+/// `Element` is a node, edge, or key element, with its data (or, for a key, its default).
+struct Element {
+    line: usize,
+    attributes: Attributes,
+    data: Attributes,
+}
+
+impl Element {
+    fn new(line: usize, attributes: Attributes) -> Self {
+        Element {
+            line,
+            attributes,
+            data: Vec::new(),
+        }
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// `Document` is the part of a GraphML document describing its first graph.
+struct Document {
+    keys: BTreeMap<String, Element>,
+    nodes: Vec<Element>,
+    edges: Vec<Element>,
+}
+
+impl Document {
+    fn read(input: &str) -> Result<Self, ParseError> {
+        let mut reader = Reader {
+            input,
+            position: 0,
+            line: 1,
+        };
+        let mut document = Document {
+            keys: BTreeMap::new(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        };
+        let mut graphs: usize = 0;
+        // The open elements, and the key, node, or edge being read.
+        let mut open: Vec<String> = Vec::new();
+        let mut current: Option<Element> = None;
+        let mut text: Option<(String, Option<String>)> = None;
+        let mut root = false;
+
+        while let Some((event, line)) = reader.next()? {
+            match event {
+                Event::Start(name, attributes, empty) => {
+                    match (open.last().map(String::as_str), name.as_str()) {
+                        (None, "graphml") if !root => root = true,
+                        (None, _) => return Err(error(line, "expected a graphml element")),
+                        (Some("graphml"), "key") => {
+                            let key = Element::new(line, attributes);
+                            if key.attribute("id").is_none() {
+                                return Err(error(line, "key has no id"));
+                            }
+                            current = Some(key);
+                        }
+                        (Some("graphml"), "graph") => graphs += 1,
+                        (Some("key"), "default") => {
+                            text = Some((String::new(), None));
+                        }
+                        (Some("graph"), "node") | (Some("graph"), "edge") if graphs == 1 => {
+                            current = Some(Element::new(line, attributes));
+                        }
+                        (Some("graph"), "hyperedge") if graphs == 1 => {
+                            return Err(error(line, "hyperedges are not supported"))
+                        }
+                        (Some("node"), "graph") if graphs == 1 => {
+                            return Err(error(line, "nested graphs are not supported"))
+                        }
+                        (Some("node"), "data") | (Some("edge"), "data") if graphs == 1 => {
+                            let key = attributes
+                                .into_iter()
+                                .find(|(attribute, _)| attribute == "key")
+                                .map(|(_, key)| key);
+                            match key {
+                                Some(key) => text = Some((String::new(), Some(key))),
+                                None => return Err(error(line, "data has no key")),
+                            }
+                        }
+                        _ => (),
+                    }
+                    open.push(name.clone());
+                    if empty {
+                        document.end(&name, &mut current, &mut text);
+                        open.pop();
+                    }
+                }
+                Event::End(name) => {
+                    if open.pop().as_ref() != Some(&name) {
+                        return Err(error(line, "end tag does not match the open element"));
+                    }
+                    document.end(&name, &mut current, &mut text);
+                }
+                Event::Text(s) => {
+                    if let Some((value, _)) = text.as_mut() {
+                        value.push_str(&s);
+                    } else if open.is_empty() && !s.trim().is_empty() {
+                        return Err(error(line, "text outside the graphml element"));
+                    }
+                }
+            }
+        }
+        if !open.is_empty() {
+            return Err(error(reader.line, "unclosed element"));
+        }
+        if !root {
+            return Err(error(reader.line, "expected a graphml element"));
+        }
+        Ok(document)
+    }
+
+    /// Finishes the element ending with the given name.
+    fn end(
+        &mut self,
+        name: &str,
+        current: &mut Option<Element>,
+        text: &mut Option<(String, Option<String>)>,
+    ) {
+        match name {
+            "key" | "node" | "edge" => {
+                if let Some(element) = current.take() {
+                    match name {
+                        "key" => {
+                            let id = String::from(element.attribute("id").unwrap_or_default());
+                            self.keys.insert(id, element);
+                        }
+                        "node" => self.nodes.push(element),
+                        _ => self.edges.push(element),
+                    }
+                }
+            }
+            "data" => {
+                if let (Some(element), Some((value, Some(key)))) = (current.as_mut(), text.take()) {
+                    element.data.push((key, value));
+                }
+            }
+            "default" => {
+                if let (Some(key), Some((value, None))) = (current.as_mut(), text.take()) {
+                    key.data.push((String::from("default"), value));
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// The data of a node, or edge, named by the `attr.name` of each key, followed by the
+    /// defaults of keys it has no data for.
+    fn data(&self, element: &Element, domain: &str) -> Result<Attributes, ParseError> {
+        let mut data: Attributes = Vec::new();
+        for (id, value) in element.data.iter() {
+            match self.keys.get(id) {
+                Some(key) => data.push((
+                    String::from(key.attribute("attr.name").unwrap_or(id)),
+                    value.clone(),
+                )),
+                None => return Err(error(element.line, "data refers to an undeclared key")),
+            }
+        }
+        for (id, key) in self.keys.iter() {
+            let applies = match key.attribute("for") {
+                Some(target) => target == domain || target == "all",
+                None => true,
+            };
+            if applies && !element.data.iter().any(|(key, _)| key == id) {
+                if let Some((_, default)) = key.data.first() {
+                    let name = key.attribute("attr.name").unwrap_or(id);
+                    data.push((String::from(name), default.clone()));
+                }
+            }
+        }
+        Ok(data)
+    }
+}
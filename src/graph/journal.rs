@@ -0,0 +1,308 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::undo::UndoLog;
+use crate::{
+    AddEdge, AddVertex, Adjacent, BTreeGraph, Connections, Edges, Error, GetEdgeValue,
+    GetVertexValue, RemoveEdge, RemoveVertex, RemovedEdges, Vertices,
+};
+
+/// `Operation` is a mutation made through one of the `AddVertex`, `AddEdge`, `RemoveEdge`,
+/// or `RemoveVertex` traits, recorded with its arguments.
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Operation<V, E> {
+    AddVertex(V),
+    AddEdge(V, V, E),
+    RemoveEdge(E),
+    RemoveVertex(V),
+}
+
+impl<V, E> Operation<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    /// Makes the mutation on the graph, e.g. to replicate a journal elsewhere.
+    pub fn apply(self, graph: &mut BTreeGraph<V, E>) -> Result<(), Error> {
+        match self {
+            Operation::AddVertex(x) => {
+                graph.add_vertex(x);
+            }
+            Operation::AddEdge(x, y, e) => {
+                graph.add_edge(x, y, e)?;
+            }
+            Operation::RemoveEdge(e) => {
+                graph.remove_edge(e)?;
+            }
+            Operation::RemoveVertex(v) => {
+                graph.remove_vertex(v)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Makes the mutation on the graph, recording what it overwrites in the log.
+    fn record(self, graph: &mut BTreeGraph<V, E>, log: &mut UndoLog<V, E>) -> Result<(), Error> {
+        match self {
+            Operation::AddVertex(x) => {
+                log.add_vertex(graph, x);
+            }
+            Operation::AddEdge(x, y, e) => {
+                log.add_edge(graph, x, y, e)?;
+            }
+            Operation::RemoveEdge(e) => {
+                log.remove_edge(graph, e)?;
+            }
+            Operation::RemoveVertex(v) => {
+                log.remove_vertex(graph, v)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `Journaled` wraps a `BTreeGraph`, keeping a journal of every mutation made through it
+/// so that mutations can be undone, redone, or drained to be replayed elsewhere. Failed
+/// mutations leave the graph untouched and are not journaled.
+pub struct Journaled<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    graph: BTreeGraph<V, E>,
+    done: Vec<(Operation<V, E>, UndoLog<V, E>)>,
+    undone: Vec<Operation<V, E>>,
+}
+
+impl<V, E> Journaled<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    pub fn new(graph: BTreeGraph<V, E>) -> Self {
+        Journaled {
+            graph,
+            done: Vec::new(),
+            undone: Vec::new(),
+        }
+    }
+
+    /// Returns the underlying graph.
+    pub fn graph(&self) -> &BTreeGraph<V, E> {
+        &self.graph
+    }
+
+    /// Unwraps the underlying graph, discarding the journal.
+    pub fn into_inner(self) -> BTreeGraph<V, E> {
+        self.graph
+    }
+
+    /// Reverts the most recent mutation. Returns false if there is nothing to undo.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, Journaled, Vertices};
+    /// let mut graph: Journaled<String, usize> = Journaled::new(BTreeGraph::new());
+    /// graph.add_vertex(String::from("origin"));
+    ///
+    /// assert!(graph.undo());
+    /// assert_eq!(graph.vertices().len(), 0);
+    /// assert!(!graph.undo());
+    /// ```
+    pub fn undo(&mut self) -> bool {
+        match self.done.pop() {
+            Some((op, log)) => {
+                log.rollback(&mut self.graph);
+                self.undone.push(op);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Makes the most recently undone mutation again. Returns false if there is nothing to
+    /// redo; any new mutation discards the mutations which could be redone.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, Journaled, Vertices};
+    /// let mut graph: Journaled<String, usize> = Journaled::new(BTreeGraph::new());
+    /// graph.add_vertex(String::from("origin"));
+    /// graph.undo();
+    ///
+    /// assert!(graph.redo().unwrap());
+    /// assert_eq!(graph.vertices().len(), 1);
+    /// assert!(!graph.redo().unwrap());
+    /// ```
+    pub fn redo(&mut self) -> Result<bool, Error> {
+        match self.undone.pop() {
+            Some(op) => {
+                self.record(op)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Removes, and returns every journaled mutation in the order they were made. The
+    /// mutations can no longer be undone, or redone.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, AddEdge, Journaled, Operation};
+    /// let mut graph: Journaled<String, usize> = Journaled::new(BTreeGraph::new());
+    /// graph.add_vertex(String::from("origin"));
+    /// graph.add_vertex(String::from("destination"));
+    /// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+    ///
+    /// // Replay the journal onto another graph.
+    /// let mut replica: BTreeGraph<String, usize> = BTreeGraph::new();
+    /// for op in graph.drain() {
+    ///     op.apply(&mut replica).unwrap();
+    /// }
+    /// assert_eq!(&replica, graph.graph());
+    /// ```
+    pub fn drain(&mut self) -> Vec<Operation<V, E>> {
+        self.undone.clear();
+        self.done.drain(..).map(|(op, _)| op).collect()
+    }
+
+    /// Makes the mutation, journaling it if it succeeds.
+    fn record(&mut self, op: Operation<V, E>) -> Result<(), Error> {
+        let mut log: UndoLog<V, E> = UndoLog::new();
+        op.clone().record(&mut self.graph, &mut log)?;
+        self.done.push((op, log));
+        Ok(())
+    }
+}
+
+impl<V, E> AddVertex<V> for Journaled<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn add_vertex(&mut self, x: V) -> bool {
+        let mut log: UndoLog<V, E> = UndoLog::new();
+        let added = log.add_vertex(&mut self.graph, x.clone());
+        // Adding a vertex which is there leaves the graph untouched, so it is not journaled.
+        if added {
+            self.undone.clear();
+            self.done.push((Operation::AddVertex(x), log));
+        }
+        added
+    }
+}
+
+impl<V, E> AddEdge<V, E> for Journaled<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    type Error = Error;
+    fn add_edge(&mut self, x: V, y: V, e: E) -> Result<Option<(V, V)>, Self::Error> {
+        let mut log: UndoLog<V, E> = UndoLog::new();
+        let prior = log.add_edge(&mut self.graph, x.clone(), y.clone(), e.clone())?;
+        self.undone.clear();
+        self.done.push((Operation::AddEdge(x, y, e), log));
+        Ok(prior)
+    }
+}
+
+impl<V, E> RemoveEdge<V, E> for Journaled<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    type Error = Error;
+    fn remove_edge(&mut self, e: E) -> Result<(V, V), Self::Error> {
+        let mut log: UndoLog<V, E> = UndoLog::new();
+        let removed = log.remove_edge(&mut self.graph, e.clone())?;
+        self.undone.clear();
+        self.done.push((Operation::RemoveEdge(e), log));
+        Ok(removed)
+    }
+}
+
+impl<V, E> RemoveVertex<V, E> for Journaled<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    type Error = Error;
+    fn remove_vertex(&mut self, v: V) -> Result<RemovedEdges<V, E>, Self::Error> {
+        let mut log: UndoLog<V, E> = UndoLog::new();
+        let removed = log.remove_vertex(&mut self.graph, v.clone())?;
+        self.undone.clear();
+        self.done.push((Operation::RemoveVertex(v), log));
+        Ok(removed)
+    }
+}
+
+impl<V, E> Vertices<V> for Journaled<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn vertices(&self) -> BTreeSet<&V> {
+        self.graph.vertices()
+    }
+}
+
+impl<V, E> Edges<E> for Journaled<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn edges(&self) -> BTreeSet<&E> {
+        self.graph.edges()
+    }
+}
+
+impl<V, E> GetEdgeValue<V, E> for Journaled<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn get_edge_value(&self, e: E) -> Option<&(V, V)> {
+        self.graph.get_edge_value(e)
+    }
+}
+
+impl<V, E> GetVertexValue<V, E> for Journaled<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn get_vertex_value(&self, v: V) -> Option<&BTreeSet<E>> {
+        self.graph.get_vertex_value(v)
+    }
+}
+
+impl<V, E> Adjacent<V> for Journaled<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    type Error = Error;
+    fn adjacent(&self, x: V, y: V) -> Result<bool, Self::Error> {
+        self.graph.adjacent(x, y)
+    }
+}
+
+impl<V, E> Connections<V> for Journaled<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    type Error = Error;
+    fn connections(&self, v: V) -> Result<BTreeSet<&V>, Self::Error> {
+        self.graph.connections(v)
+    }
+}
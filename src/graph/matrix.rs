@@ -0,0 +1,194 @@
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{BTreeGraph, Error};
+
+/// `Csr` is the adjacency matrix of a graph in compressed sparse row form, with an entry
+/// for each edge. The edges from the vertex at index i are `edges[offsets[i]..offsets[i +
+/// 1]]`, in ascending order, and go to the vertices at the same indices of `targets`.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Csr<'g, V, E> {
+    /// The vertices, in ascending order, giving the index of each.
+    pub vertices: Vec<&'g V>,
+    /// The start of the edges of each vertex, followed by the number of edges.
+    pub offsets: Vec<usize>,
+    /// The index of the target of each edge.
+    pub targets: Vec<usize>,
+    /// The edges, grouped by source.
+    pub edges: Vec<&'g E>,
+}
+
+/// Matrix conversions. Vertices are numbered in ascending order, as returned with each
+/// matrix, and matrices are laid out in row-major order.
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn index(&self) -> (Vec<&V>, BTreeMap<&V, usize>) {
+        let vertices: Vec<&V> = self.vertices.keys().collect();
+        let index = vertices.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        (vertices, index)
+    }
+
+    /// Returns the vertices, and the adjacency matrix of the graph, with the number of
+    /// edges from the vertex at index i to the vertex at index j in entry `i * n + j`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, AddEdge};
+    /// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+    /// graph.add_vertex(String::from("origin"));
+    /// graph.add_vertex(String::from("destination"));
+    /// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+    ///
+    /// let (vertices, matrix) = graph.to_adjacency_matrix();
+    /// assert_eq!(vertices, vec!["destination", "origin"]);
+    /// assert_eq!(matrix, vec![0, 0, 1, 0]);
+    /// ```
+    pub fn to_adjacency_matrix(&self) -> (Vec<&V>, Vec<usize>) {
+        let (vertices, index) = self.index();
+        let n = vertices.len();
+        let mut matrix = vec![0; n * n];
+        for (x, y) in self.edges.values() {
+            matrix[index[x] * n + index[y]] += 1;
+        }
+        (vertices, matrix)
+    }
+
+    /// Returns the adjacency matrix of the graph in compressed sparse row form.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, AddEdge};
+    /// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+    /// graph.add_vertex(String::from("origin"));
+    /// graph.add_vertex(String::from("destination"));
+    /// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+    ///
+    /// let csr = graph.to_csr();
+    /// assert_eq!(csr.offsets, vec![0, 0, 1]);
+    /// assert_eq!(csr.targets, vec![0]);
+    /// assert_eq!(csr.edges, vec![&10]);
+    /// ```
+    pub fn to_csr(&self) -> Csr<'_, V, E> {
+        let (vertices, index) = self.index();
+        let mut offsets: Vec<usize> = Vec::with_capacity(vertices.len() + 1);
+        let mut targets: Vec<usize> = Vec::with_capacity(self.edges.len());
+        let mut edges: Vec<&E> = Vec::with_capacity(self.edges.len());
+        offsets.push(0);
+        for adjacent in self.vertices.values() {
+            for (e, (_, y)) in adjacent.iter().filter_map(|e| self.edges.get_key_value(e)) {
+                targets.push(index[y]);
+                edges.push(e);
+            }
+            offsets.push(edges.len());
+        }
+        Csr {
+            vertices,
+            offsets,
+            targets,
+            edges,
+        }
+    }
+
+    /// Returns the vertices, the edges, and the incidence matrix of the graph, with a row
+    /// for each vertex, and a column for each edge. The entry for an edge is -1 in the row
+    /// of its source, 1 in the row of its target, and 0 elsewhere, including for a loop.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, AddEdge};
+    /// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+    /// graph.add_vertex(String::from("origin"));
+    /// graph.add_vertex(String::from("destination"));
+    /// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+    ///
+    /// let (_, edges, matrix) = graph.to_incidence_matrix();
+    /// assert_eq!(edges, vec![&10]);
+    /// assert_eq!(matrix, vec![1, -1]);
+    /// ```
+    pub fn to_incidence_matrix(&self) -> (Vec<&V>, Vec<&E>, Vec<i8>) {
+        let (vertices, index) = self.index();
+        let m = self.edges.len();
+        let mut matrix = vec![0; vertices.len() * m];
+        for (j, (x, y)) in self.edges.values().enumerate() {
+            matrix[index[x] * m + j] -= 1;
+            matrix[index[y] * m + j] += 1;
+        }
+        (vertices, self.edges.keys().collect(), matrix)
+    }
+}
+
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    /// Builds a graph from vertices, and an adjacency matrix, as returned by
+    /// `to_adjacency_matrix`, with as many edges from the vertex at index i to the vertex
+    /// at index j as entry `i * n + j`. Each edge is named by the closure, which is given
+    /// its source, and target. Raises `Error::ImproperDimension` if the matrix is not n by
+    /// n, `Error::VertexExists` if a vertex is given more than once, and
+    /// `Error::EdgeExists` if the closure names edges the same.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, Adjacent};
+    /// let labels = vec![String::from("destination"), String::from("origin")];
+    /// let mut count = 0;
+    /// let graph: BTreeGraph<String, usize> = BTreeGraph::from_adjacency_matrix(
+    ///     labels,
+    ///     &[0, 0, 1, 0],
+    ///     |_, _| {
+    ///         count += 1;
+    ///         count
+    ///     },
+    /// ).unwrap();
+    ///
+    /// assert!(graph.adjacent(String::from("origin"), String::from("destination")).unwrap());
+    /// ```
+    pub fn from_adjacency_matrix<F>(
+        labels: Vec<V>,
+        matrix: &[usize],
+        mut edge: F,
+    ) -> Result<Self, Error>
+    where
+        F: FnMut(&V, &V) -> E,
+    {
+        let n = labels.len();
+        if matrix.len() != n * n {
+            return Err(Error::ImproperDimension);
+        }
+        let mut graph: BTreeGraph<V, E> = BTreeGraph::new();
+        for v in labels.iter() {
+            if graph
+                .vertices
+                .insert(v.clone(), Default::default())
+                .is_some()
+            {
+                return Err(Error::VertexExists);
+            }
+        }
+        for (i, x) in labels.iter().enumerate() {
+            for (j, y) in labels.iter().enumerate() {
+                for _ in 0..matrix[i * n + j] {
+                    let e = edge(x, y);
+                    if graph.edges.contains_key(&e) {
+                        return Err(Error::EdgeExists);
+                    }
+                    if let Some(adjacent) = graph.vertices.get_mut(x) {
+                        adjacent.insert(e.clone());
+                    }
+                    graph.edges.insert(e, (x.clone(), y.clone()));
+                }
+            }
+        }
+        Ok(graph)
+    }
+}
@@ -1,7 +1,35 @@
 mod api;
+mod binary;
+mod bulk;
+#[cfg(feature = "serde")]
+mod de;
+mod degree;
+mod diff;
+#[cfg(feature = "fmt")]
+mod display;
+pub mod dot;
+#[cfg(any(feature = "serde_json", feature = "serde_yaml", feature = "serde_cbor"))]
+mod encoding;
+mod entry;
+mod frozen;
+mod graphml;
+mod journal;
+mod matrix;
+mod observer;
+#[cfg(feature = "persistent")]
+mod persistent;
+#[cfg(feature = "serde")]
+pub mod readable;
+mod set;
+mod subgraph;
 mod test;
+mod text;
+mod transaction;
+mod undo;
+mod validate;
+mod view;
 
-use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::collections::{btree_map, BTreeMap, BTreeSet};
 use core::default::Default;
 
 #[cfg(feature = "serde")]
@@ -9,13 +37,101 @@ use serde::{Deserialize, Serialize};
 
 use crate::Error;
 pub use api::*;
+pub use binary::*;
+pub use diff::*;
+#[cfg(feature = "fmt")]
+pub use display::*;
+pub use entry::*;
+pub use frozen::*;
+pub use journal::*;
+pub use matrix::*;
+pub use observer::*;
+#[cfg(feature = "persistent")]
+pub use persistent::*;
+pub use set::*;
+pub use transaction::*;
+pub use validate::*;
+pub use view::*;
 
 pub type Edge<V, E> = (E, (V, V));
 
+/// `RemovedEdges` lists the edges removed along with a vertex, by their direction relative
+/// to it.
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RemovedEdges<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    /// Edges from another vertex to the removed vertex.
+    pub incoming: BTreeSet<Edge<V, E>>,
+    /// Edges from the removed vertex to another vertex.
+    pub outgoing: BTreeSet<Edge<V, E>>,
+    /// Edges from the removed vertex to itself.
+    pub loops: BTreeSet<Edge<V, E>>,
+}
+
+impl<V, E> RemovedEdges<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    pub fn new() -> Self {
+        RemovedEdges {
+            incoming: BTreeSet::new(),
+            outgoing: BTreeSet::new(),
+            loops: BTreeSet::new(),
+        }
+    }
+
+    /// Returns the number of edges removed.
+    pub fn len(&self) -> usize {
+        self.incoming.len() + self.outgoing.len() + self.loops.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over every edge removed; incoming, then outgoing, then loops.
+    pub fn iter(&self) -> impl Iterator<Item = &Edge<V, E>> {
+        self.incoming
+            .iter()
+            .chain(self.outgoing.iter())
+            .chain(self.loops.iter())
+    }
+}
+
+impl<V, E> Default for RemovedEdges<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `ParseError` is raised when the text given to one of the graph parsers is malformed,
+/// along with the line, counting from 1, the problem was found on.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: &'static str,
+}
+
+#[cfg(feature = "fmt")]
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "Parse Error: line {}: {}", self.line, self.message)
+    }
+}
+
 /// `BTreeGraph` is an implementation of a graph (abstract data structure)
 /// which utilizes `BTreeMap` for the edge and vertex adjacency lists.
 #[derive(PartialEq, Eq, Clone, Debug)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct BTreeGraph<V, E>
 where
     V: Ord,
@@ -35,6 +151,25 @@ where
         let edges: BTreeMap<E, (V, V)> = BTreeMap::new();
         BTreeGraph { vertices, edges }
     }
+
+    /// Sets the value of the edge e, which the adjacency list of its new source already
+    /// holds. If the edge moved to another source, it leaves the old adjacency list.
+    fn insert_edge_value(&mut self, e: E, value: (V, V)) -> Option<(V, V)> {
+        match self.edges.entry(e) {
+            btree_map::Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            }
+            btree_map::Entry::Occupied(mut entry) => {
+                if entry.get().0 != value.0 {
+                    if let Some(edges) = self.vertices.get_mut(&entry.get().0) {
+                        edges.remove(entry.key());
+                    }
+                }
+                Some(entry.insert(value))
+            }
+        }
+    }
 }
 
 impl<V, E> Default for BTreeGraph<V, E>
@@ -67,13 +202,38 @@ where
     }
 }
 
-impl<V, E> AddVertex<V, E> for BTreeGraph<V, E>
+impl<V, E> AddVertex<V> for BTreeGraph<V, E>
 where
     V: Ord,
     E: Ord,
 {
-    fn add_vertex(&mut self, x: V) -> Option<BTreeSet<E>> {
-        self.vertices.insert(x, BTreeSet::new())
+    fn add_vertex(&mut self, x: V) -> bool {
+        if self.vertices.contains_key(&x) {
+            return false;
+        }
+        self.vertices.insert(x, BTreeSet::new());
+        true
+    }
+}
+
+/// When you reset a vertex, you should remove its edges too, so none are left dangling.
+impl<V, E> ResetVertex<V, E> for BTreeGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn reset_vertex(&mut self, x: V) -> Option<BTreeSet<Edge<V, E>>> {
+        let outgoing = self.vertices.insert(x, BTreeSet::new())?;
+        Some(
+            outgoing
+                .into_iter()
+                .map(|edge| {
+                    // Edges in an adjacency list always exist, so it is safe to unwrap here.
+                    let value = self.edges.remove(&edge).unwrap();
+                    (edge, value)
+                })
+                .collect(),
+        )
     }
 }
 
@@ -85,18 +245,34 @@ where
 {
     type Error = Error;
     fn add_edge(&mut self, x: V, y: V, e: E) -> Result<Option<(V, V)>, Self::Error> {
-        if self.vertices.get(&y).is_some() {
-            if let Some(edges) = self.vertices.get(&x) {
-                let mut v: BTreeSet<E> = edges.iter().cloned().collect();
-                v.insert(e.clone());
-                self.vertices.insert(x.clone(), v);
-                return Ok(self.edges.insert(e, (x, y)));
+        if self.vertices.contains_key(&y) {
+            if let Some(edges) = self.vertices.get_mut(&x) {
+                edges.insert(e.clone());
+                return Ok(self.insert_edge_value(e, (x, y)));
             }
         }
         Err(Error::VertexDoesNotExist)
     }
 }
 
+/// Vertices which do not exist are added, rather than raising an error.
+impl<V, E> AddEdgeWithVertices<V, E> for BTreeGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn add_edge_with_vertices(&mut self, x: V, y: V, e: E) -> Option<(V, V)> {
+        if !self.vertices.contains_key(&y) {
+            self.vertices.insert(y.clone(), BTreeSet::new());
+        }
+        self.vertices
+            .entry(x.clone())
+            .or_default()
+            .insert(e.clone());
+        self.insert_edge_value(e, (x, y))
+    }
+}
+
 impl<V, E> GetEdgeValue<V, E> for BTreeGraph<V, E>
 where
     V: Ord,
@@ -127,11 +303,8 @@ where
     type Error = Error;
     fn remove_edge(&mut self, e: E) -> Result<(V, V), Self::Error> {
         if let Some(edge) = self.edges.get(&e) {
-            let v = edge.0.clone();
-            if let Some(vertex) = self.vertices.get(&v) {
-                let mut vertex = vertex.clone();
+            if let Some(vertex) = self.vertices.get_mut(&edge.0) {
                 vertex.remove(&e);
-                self.vertices.insert(v, vertex);
             }
             // We have already checked e exists in the edges so it is
             // safe to unwrap.
@@ -148,36 +321,65 @@ where
     E: Ord + Clone,
 {
     type Error = Error;
-    fn remove_vertex(&mut self, v: V) -> Result<BTreeSet<Edge<V, E>>, Self::Error> {
+    fn remove_vertex(&mut self, v: V) -> Result<RemovedEdges<V, E>, Self::Error> {
         // When removing an vertex, of course, we should remove
         // all adjacent edges;
-        if let Some(edges) = self.vertices.get(&v) {
-            for edge in edges {
-                self.edges.remove(&edge);
+        if let Some(outgoing) = self.vertices.remove(&v) {
+            let mut removed: RemovedEdges<V, E> = RemovedEdges::new();
+            for edge in outgoing {
+                // Edges in an adjacency list always exist, so it is safe to unwrap here.
+                let value = self.edges.remove(&edge).unwrap();
+                if value.1 == v {
+                    removed.loops.insert((edge, value));
+                } else {
+                    removed.outgoing.insert((edge, value));
+                }
             }
             // in addition we should be checking for, and removing
             // any edges which point to the vertex pending removal.
-            let edges_to_remove: BTreeSet<(E, (V, V))> = self.edges
-                .clone()
-                .into_iter()
-                .filter(|e| -> bool { e.1.1 == v })
+            let incoming: BTreeSet<E> = self
+                .edges
+                .iter()
+                .filter(|(_, value)| value.1 == v)
+                .map(|(edge, _)| edge.clone())
                 .collect();
 
-            for edge in edges_to_remove.iter().cloned() {
-                self.remove_edge(edge.0)?;
+            for edge in incoming {
+                let value = self.edges.remove(&edge).unwrap();
+                if let Some(edges) = self.vertices.get_mut(&value.0) {
+                    edges.remove(&edge);
+                }
+                removed.incoming.insert((edge, value));
             }
 
-            // We have already checked v exists in vertices, so it is safe to unwrap here.
-            self.vertices.remove(&v).unwrap();
-
             // Return the edges which were removed in case the user needs them (possibly to add
             // a subset of them back).
-            return Ok(edges_to_remove);
+            return Ok(removed);
         }
         Err(Error::VertexDoesNotExist)
     }
 }
 
+impl<V, E> TryRemoveVertex<V> for BTreeGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    type Error = Error;
+    fn try_remove_vertex(&mut self, v: V) -> Result<(), Self::Error> {
+        match self.vertices.get(&v) {
+            Some(edges) => {
+                if !edges.is_empty() || self.edges.values().any(|value| value.1 == v) {
+                    return Err(Error::EdgeExists);
+                }
+                self.vertices.remove(&v);
+                Ok(())
+            }
+            None => Err(Error::VertexDoesNotExist),
+        }
+    }
+}
+
 
 impl<V, E> Adjacent<V> for BTreeGraph<V, E>
 where
@@ -186,12 +388,12 @@ where
 {
     type Error = Error;
     fn adjacent(&self, x: V, y: V) -> Result<bool, Self::Error> {
-        if self.vertices.get(&y).is_some() {
+        if self.vertices.contains_key(&y) {
             if let Some(edges) = self.vertices.get(&x) {
                 for edge in edges {
                     // We can assume an edge exists if it is found adjacent
                     // to some vertex.
-                    let e = self.edges.get(&edge).unwrap();
+                    let e = self.edges.get(edge).unwrap();
                     if e.0 == x && e.1 == y {
                         return Ok(true);
                     }
@@ -213,7 +415,7 @@ where
         match self.vertices.get(&v) {
             Some(vertex) => Ok(vertex
                 .iter()
-                .map(|edge| -> &V { &self.edges.get(&edge).unwrap().1 })
+                .map(|edge| -> &V { &self.edges.get(edge).unwrap().1 })
                 .collect()),
             None => Err(Error::VertexDoesNotExist),
         }
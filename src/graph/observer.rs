@@ -0,0 +1,231 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::{
+    AddEdge, AddVertex, Adjacent, BTreeGraph, Connections, Edges, Error, GetEdgeValue,
+    GetVertexValue, RemoveEdge, RemoveVertex, RemovedEdges, Vertices,
+};
+
+/// `GraphEvent` describes a change made to a graph.
+#[derive(PartialEq, Eq, Debug)]
+pub enum GraphEvent<'a, V, E> {
+    /// A vertex which did not exist was added.
+    VertexAdded(&'a V),
+    /// A vertex was removed; any edges incident to it are removed beforehand.
+    VertexRemoved(&'a V),
+    /// An edge which did not exist was added (the edge, and its value).
+    EdgeAdded(&'a E, &'a (V, V)),
+    /// An existing edge was added again (the edge, its old value, and its new value).
+    EdgeReplaced(&'a E, &'a (V, V), &'a (V, V)),
+    /// An edge was removed, either directly or by removing one of its vertices.
+    EdgeRemoved(&'a E, &'a (V, V)),
+}
+
+/// `GraphObserver` is notified of every change made to an `Observed` graph. It is
+/// implemented for any closure taking a `&GraphEvent`.
+pub trait GraphObserver<V, E> {
+    fn notify(&mut self, event: &GraphEvent<V, E>);
+}
+
+impl<V, E, F> GraphObserver<V, E> for F
+where
+    F: FnMut(&GraphEvent<V, E>),
+{
+    fn notify(&mut self, event: &GraphEvent<V, E>) {
+        self(event)
+    }
+}
+
+/// `Observed` wraps a `BTreeGraph`, notifying every registered `GraphObserver` of the
+/// changes made through it, in the order they are made.
+pub struct Observed<'o, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    graph: BTreeGraph<V, E>,
+    observers: Vec<Box<dyn GraphObserver<V, E> + 'o>>,
+}
+
+impl<'o, V, E> Observed<'o, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    pub fn new(graph: BTreeGraph<V, E>) -> Self {
+        Observed {
+            graph,
+            observers: Vec::new(),
+        }
+    }
+
+    /// Registers an observer to be notified of every subsequent change.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, GraphEvent, Observed};
+    /// let mut added: usize = 0;
+    /// let mut graph: Observed<String, usize> = Observed::new(BTreeGraph::new());
+    /// graph.register(|event: &GraphEvent<String, usize>| {
+    ///     if let GraphEvent::VertexAdded(_) = event {
+    ///         added += 1;
+    ///     }
+    /// });
+    /// graph.add_vertex(String::from("origin"));
+    /// graph.add_vertex(String::from("destination"));
+    /// drop(graph);
+    ///
+    /// assert_eq!(added, 2);
+    /// ```
+    pub fn register<O>(&mut self, observer: O)
+    where
+        O: GraphObserver<V, E> + 'o,
+    {
+        self.observers.push(Box::new(observer));
+    }
+
+    /// Returns the underlying graph.
+    pub fn graph(&self) -> &BTreeGraph<V, E> {
+        &self.graph
+    }
+
+    /// Unwraps the underlying graph, discarding the observers.
+    pub fn into_inner(self) -> BTreeGraph<V, E> {
+        self.graph
+    }
+}
+
+fn notify<V, E>(observers: &mut [Box<dyn GraphObserver<V, E> + '_>], event: GraphEvent<V, E>) {
+    for observer in observers.iter_mut() {
+        observer.notify(&event);
+    }
+}
+
+impl<'o, V, E> AddVertex<V> for Observed<'o, V, E>
+where
+    V: Ord + Clone,
+    E: Ord,
+{
+    fn add_vertex(&mut self, x: V) -> bool {
+        let added = self.graph.add_vertex(x.clone());
+        if added {
+            notify(&mut self.observers, GraphEvent::VertexAdded(&x));
+        }
+        added
+    }
+}
+
+impl<'o, V, E> AddEdge<V, E> for Observed<'o, V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    type Error = Error;
+    fn add_edge(&mut self, x: V, y: V, e: E) -> Result<Option<(V, V)>, Self::Error> {
+        let old = self.graph.add_edge(x, y, e.clone())?;
+        // The edge was just added so it is safe to unwrap.
+        let value = self.graph.edges.get(&e).unwrap();
+        match old.as_ref() {
+            Some(old) => notify(
+                &mut self.observers,
+                GraphEvent::EdgeReplaced(&e, old, value),
+            ),
+            None => notify(&mut self.observers, GraphEvent::EdgeAdded(&e, value)),
+        }
+        Ok(old)
+    }
+}
+
+impl<'o, V, E> RemoveEdge<V, E> for Observed<'o, V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    type Error = Error;
+    fn remove_edge(&mut self, e: E) -> Result<(V, V), Self::Error> {
+        let value = self.graph.remove_edge(e.clone())?;
+        notify(&mut self.observers, GraphEvent::EdgeRemoved(&e, &value));
+        Ok(value)
+    }
+}
+
+impl<'o, V, E> RemoveVertex<V, E> for Observed<'o, V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    type Error = Error;
+    fn remove_vertex(&mut self, v: V) -> Result<RemovedEdges<V, E>, Self::Error> {
+        let removed = self.graph.remove_vertex(v.clone())?;
+        // Every edge the removal cascaded to is reported before the vertex itself.
+        for (e, value) in removed.iter() {
+            notify(&mut self.observers, GraphEvent::EdgeRemoved(e, value));
+        }
+        notify(&mut self.observers, GraphEvent::VertexRemoved(&v));
+        Ok(removed)
+    }
+}
+
+impl<'o, V, E> Vertices<V> for Observed<'o, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn vertices(&self) -> BTreeSet<&V> {
+        self.graph.vertices()
+    }
+}
+
+impl<'o, V, E> Edges<E> for Observed<'o, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn edges(&self) -> BTreeSet<&E> {
+        self.graph.edges()
+    }
+}
+
+impl<'o, V, E> GetEdgeValue<V, E> for Observed<'o, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn get_edge_value(&self, e: E) -> Option<&(V, V)> {
+        self.graph.get_edge_value(e)
+    }
+}
+
+impl<'o, V, E> GetVertexValue<V, E> for Observed<'o, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn get_vertex_value(&self, v: V) -> Option<&BTreeSet<E>> {
+        self.graph.get_vertex_value(v)
+    }
+}
+
+impl<'o, V, E> Adjacent<V> for Observed<'o, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    type Error = Error;
+    fn adjacent(&self, x: V, y: V) -> Result<bool, Self::Error> {
+        self.graph.adjacent(x, y)
+    }
+}
+
+impl<'o, V, E> Connections<V> for Observed<'o, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    type Error = Error;
+    fn connections(&self, v: V) -> Result<BTreeSet<&V>, Self::Error> {
+        self.graph.connections(v)
+    }
+}
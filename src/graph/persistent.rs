@@ -0,0 +1,610 @@
+use alloc::collections::BTreeSet;
+use core::default::Default;
+
+use rpds::{RedBlackTreeMap, RedBlackTreeSet};
+
+use super::diff::{self, Maps};
+
+use crate::{
+    AddEdge, AddEdgeWithVertices, AddVertex, Adjacent, ApplyDiff, BTreeGraph, Conflict,
+    ConflictPolicy, Connections, Diff, Difference, Edge, EdgeSubgraph, Edges, Error, FilterMap,
+    GetEdgeValue, GraphDiff, InducedSubgraph, Intersection, IsolatedVertices, RemoveEdge,
+    RemoveIsolatedVertices, RemoveVertex, RemovedEdges, ResetVertex, Sinks, Sources,
+    SymmetricDifference, TryRemoveVertex, Union, Vertices,
+};
+
+/// `PersistentGraph` is an implementation of a graph with the graph traits of `BTreeGraph`,
+/// but which utilizes a persistent `RedBlackTreeMap` for the edges, and a persistent
+/// `RedBlackTreeMap` of `RedBlackTreeSet`s for the vertex adjacency lists. Snapshots share
+/// structure with the graph they were taken from, so taking one is O(1) and a mutation
+/// afterwards only copies the paths of the trees it touches, including within the
+/// adjacency list of a vertex.
+///
+/// As the adjacency lists are not `BTreeSet`s, `GetVertexValue` is the one trait not
+/// implemented; the inherent `get_vertex_value` returns the persistent set instead. Set
+/// operations convert both graphs to `BTreeGraph`s, so the result shares no structure with
+/// either.
+#[derive(PartialEq, Eq, Debug)]
+pub struct PersistentGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    vertices: RedBlackTreeMap<V, RedBlackTreeSet<E>>,
+    edges: RedBlackTreeMap<E, (V, V)>,
+}
+
+impl<V, E> PersistentGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    pub fn new() -> Self {
+        let vertices: RedBlackTreeMap<V, RedBlackTreeSet<E>> = RedBlackTreeMap::new();
+        let edges: RedBlackTreeMap<E, (V, V)> = RedBlackTreeMap::new();
+        PersistentGraph { vertices, edges }
+    }
+
+    /// Returns a snapshot of the graph in O(1). Neither the graph, nor the snapshot
+    /// observe mutations made to the other.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{AddVertex, PersistentGraph, Vertices};
+    /// let mut graph: PersistentGraph<String, usize> = PersistentGraph::new();
+    /// graph.add_vertex(String::from("origin"));
+    ///
+    /// let snapshot = graph.snapshot();
+    /// graph.add_vertex(String::from("destination"));
+    ///
+    /// assert_eq!(snapshot.vertices().len(), 1);
+    /// assert_eq!(graph.vertices().len(), 2);
+    /// ```
+    pub fn snapshot(&self) -> Self {
+        self.clone()
+    }
+
+    /// Returns the adjacency list of the vertex v, as with `GetVertexValue`.
+    pub fn get_vertex_value(&self, v: V) -> Option<&RedBlackTreeSet<E>> {
+        self.vertices.get(&v)
+    }
+
+    /// Collects every vertex which is the destination of at least one edge.
+    fn targets(&self) -> BTreeSet<&V> {
+        self.edges.values().map(|(_, y)| y).collect()
+    }
+}
+
+impl<V, E> Clone for PersistentGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn clone(&self) -> Self {
+        PersistentGraph {
+            vertices: self.vertices.clone(),
+            edges: self.edges.clone(),
+        }
+    }
+}
+
+impl<V, E> Default for PersistentGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, E> From<BTreeGraph<V, E>> for PersistentGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn from(graph: BTreeGraph<V, E>) -> Self {
+        PersistentGraph {
+            vertices: graph
+                .vertices
+                .into_iter()
+                .map(|(v, edges)| (v, edges.into_iter().collect()))
+                .collect(),
+            edges: graph.edges.into_iter().collect(),
+        }
+    }
+}
+
+impl<V, E> From<&PersistentGraph<V, E>> for BTreeGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn from(graph: &PersistentGraph<V, E>) -> Self {
+        BTreeGraph {
+            vertices: graph
+                .vertices
+                .iter()
+                .map(|(v, edges)| (v.clone(), edges.iter().cloned().collect()))
+                .collect(),
+            edges: graph
+                .edges
+                .iter()
+                .map(|(e, value)| (e.clone(), value.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl<V, E> Vertices<V> for PersistentGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn vertices(&self) -> BTreeSet<&V> {
+        self.vertices.keys().collect()
+    }
+}
+
+impl<V, E> Edges<E> for PersistentGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn edges(&self) -> BTreeSet<&E> {
+        self.edges.keys().collect()
+    }
+}
+
+impl<V, E> AddVertex<V> for PersistentGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn add_vertex(&mut self, x: V) -> bool {
+        if self.vertices.contains_key(&x) {
+            return false;
+        }
+        self.vertices.insert_mut(x, RedBlackTreeSet::new());
+        true
+    }
+}
+
+impl<V, E> ResetVertex<V, E> for PersistentGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn reset_vertex(&mut self, x: V) -> Option<BTreeSet<Edge<V, E>>> {
+        let outgoing = self.vertices.get(&x).cloned();
+        self.vertices.insert_mut(x, RedBlackTreeSet::new());
+        Some(
+            outgoing?
+                .iter()
+                .map(|edge| {
+                    // Edges in an adjacency list always exist, so it is safe to unwrap here.
+                    let value = self.edges.get(edge).cloned().unwrap();
+                    self.edges.remove_mut(edge);
+                    (edge.clone(), value)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<V, E> AddEdge<V, E> for PersistentGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    type Error = Error;
+    fn add_edge(&mut self, x: V, y: V, e: E) -> Result<Option<(V, V)>, Self::Error> {
+        if self.vertices.contains_key(&y) {
+            if let Some(edges) = self.vertices.get_mut(&x) {
+                edges.insert_mut(e.clone());
+                let old = self.edges.get(&e).cloned();
+                if let Some(old) = old.as_ref() {
+                    // The edge moved to another source, so it leaves the old adjacency list.
+                    if old.0 != x {
+                        if let Some(edges) = self.vertices.get_mut(&old.0) {
+                            edges.remove_mut(&e);
+                        }
+                    }
+                }
+                self.edges.insert_mut(e, (x, y));
+                return Ok(old);
+            }
+        }
+        Err(Error::VertexDoesNotExist)
+    }
+}
+
+impl<V, E> GetEdgeValue<V, E> for PersistentGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn get_edge_value(&self, e: E) -> Option<&(V, V)> {
+        self.edges.get(&e)
+    }
+}
+
+impl<V, E> RemoveEdge<V, E> for PersistentGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    type Error = Error;
+    fn remove_edge(&mut self, e: E) -> Result<(V, V), Self::Error> {
+        if let Some(edge) = self.edges.get(&e).cloned() {
+            if let Some(vertex) = self.vertices.get_mut(&edge.0) {
+                vertex.remove_mut(&e);
+            }
+            self.edges.remove_mut(&e);
+            return Ok(edge);
+        }
+        Err(Error::EdgeDoesNotExist)
+    }
+}
+
+impl<V, E> RemoveVertex<V, E> for PersistentGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    type Error = Error;
+    fn remove_vertex(&mut self, v: V) -> Result<RemovedEdges<V, E>, Self::Error> {
+        if let Some(outgoing) = self.vertices.get(&v).cloned() {
+            let mut removed: RemovedEdges<V, E> = RemovedEdges::new();
+            self.vertices.remove_mut(&v);
+            for edge in outgoing.iter() {
+                // Edges in an adjacency list always exist, so it is safe to unwrap here.
+                let value = self.edges.get(edge).cloned().unwrap();
+                self.edges.remove_mut(edge);
+                if value.1 == v {
+                    removed.loops.insert((edge.clone(), value));
+                } else {
+                    removed.outgoing.insert((edge.clone(), value));
+                }
+            }
+            // Any edges which point to the vertex pending removal are also removed.
+            let incoming: BTreeSet<E> = self
+                .edges
+                .iter()
+                .filter(|(_, value)| value.1 == v)
+                .map(|(edge, _)| edge.clone())
+                .collect();
+
+            for edge in incoming {
+                let value = self.remove_edge(edge.clone())?;
+                removed.incoming.insert((edge, value));
+            }
+            return Ok(removed);
+        }
+        Err(Error::VertexDoesNotExist)
+    }
+}
+
+impl<V, E> TryRemoveVertex<V> for PersistentGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    type Error = Error;
+    fn try_remove_vertex(&mut self, v: V) -> Result<(), Self::Error> {
+        match self.vertices.get(&v) {
+            Some(edges) => {
+                if !edges.is_empty() || self.edges.values().any(|value| value.1 == v) {
+                    return Err(Error::EdgeExists);
+                }
+                self.vertices.remove_mut(&v);
+                Ok(())
+            }
+            None => Err(Error::VertexDoesNotExist),
+        }
+    }
+}
+
+impl<V, E> Adjacent<V> for PersistentGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    type Error = Error;
+    fn adjacent(&self, x: V, y: V) -> Result<bool, Self::Error> {
+        if self.vertices.contains_key(&y) {
+            if let Some(edges) = self.vertices.get(&x) {
+                // We can assume an edge exists if it is found adjacent
+                // to some vertex.
+                return Ok(edges
+                    .iter()
+                    .any(|edge| self.edges.get(edge).unwrap().1 == y));
+            }
+        }
+        Err(Error::VertexDoesNotExist)
+    }
+}
+
+impl<V, E> Connections<V> for PersistentGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    type Error = Error;
+    fn connections(&self, v: V) -> Result<BTreeSet<&V>, Self::Error> {
+        match self.vertices.get(&v) {
+            Some(vertex) => Ok(vertex
+                .iter()
+                .map(|edge| -> &V { &self.edges.get(edge).unwrap().1 })
+                .collect()),
+            None => Err(Error::VertexDoesNotExist),
+        }
+    }
+}
+
+/// Vertices which do not exist are added, rather than raising an error.
+impl<V, E> AddEdgeWithVertices<V, E> for PersistentGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn add_edge_with_vertices(&mut self, x: V, y: V, e: E) -> Option<(V, V)> {
+        self.add_vertex(x.clone());
+        self.add_vertex(y.clone());
+        // Both vertices exist now, so it is safe to unwrap here.
+        self.add_edge(x, y, e).unwrap()
+    }
+}
+
+impl<V, E> Sources<V> for PersistentGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn sources<'a>(&'a self) -> impl Iterator<Item = &'a V>
+    where
+        V: 'a,
+    {
+        let targets = self.targets();
+        self.vertices.keys().filter(move |v| !targets.contains(v))
+    }
+}
+
+impl<V, E> Sinks<V> for PersistentGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn sinks<'a>(&'a self) -> impl Iterator<Item = &'a V>
+    where
+        V: 'a,
+    {
+        self.vertices
+            .iter()
+            .filter(|(_, edges)| edges.is_empty())
+            .map(|(v, _)| v)
+    }
+}
+
+impl<V, E> IsolatedVertices<V> for PersistentGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn isolated_vertices<'a>(&'a self) -> impl Iterator<Item = &'a V>
+    where
+        V: 'a,
+    {
+        let targets = self.targets();
+        self.sinks().filter(move |v| !targets.contains(v))
+    }
+}
+
+impl<V, E> RemoveIsolatedVertices<V> for PersistentGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord,
+{
+    fn remove_isolated_vertices(&mut self) -> BTreeSet<V> {
+        let isolated: BTreeSet<V> = self.isolated_vertices().cloned().collect();
+        for v in isolated.iter() {
+            self.vertices.remove_mut(v);
+        }
+        isolated
+    }
+}
+
+impl<V, E> InducedSubgraph<V> for PersistentGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn induced_subgraph(&self, x: &BTreeSet<V>) -> Self {
+        let mut subgraph: PersistentGraph<V, E> = PersistentGraph::new();
+        for (v, adjacent) in x.iter().filter_map(|v| self.vertices.get_key_value(v)) {
+            let mut retained: RedBlackTreeSet<E> = RedBlackTreeSet::new();
+            for edge in adjacent.iter() {
+                // We can assume an edge exists if it is found adjacent
+                // to some vertex.
+                let value = self.edges.get(edge).unwrap();
+                if x.contains(&value.1) {
+                    retained.insert_mut(edge.clone());
+                    subgraph.edges.insert_mut(edge.clone(), value.clone());
+                }
+            }
+            subgraph.vertices.insert_mut(v.clone(), retained);
+        }
+        subgraph
+    }
+}
+
+impl<V, E> EdgeSubgraph<E> for PersistentGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn edge_subgraph(&self, x: &BTreeSet<E>) -> Self {
+        let mut subgraph: PersistentGraph<V, E> = PersistentGraph::new();
+        for (edge, (from, to)) in x.iter().filter_map(|e| self.edges.get_key_value(e)) {
+            subgraph.add_edge_with_vertices(from.clone(), to.clone(), edge.clone());
+        }
+        subgraph
+    }
+}
+
+impl<V, E> FilterMap<V, E> for PersistentGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn filter_map<P, Q>(&self, mut vertex_pred: P, mut edge_pred: Q) -> Self
+    where
+        P: FnMut(&V) -> bool,
+        Q: FnMut(&E, &(V, V)) -> bool,
+    {
+        let mut subgraph: PersistentGraph<V, E> = PersistentGraph::new();
+        for v in self.vertices.keys().filter(|v| vertex_pred(v)) {
+            subgraph.add_vertex(v.clone());
+        }
+        for (edge, value) in self.edges.iter() {
+            if !subgraph.vertices.contains_key(&value.1) {
+                continue;
+            }
+            if let Some(adjacent) = subgraph.vertices.get_mut(&value.0) {
+                if edge_pred(edge, value) {
+                    adjacent.insert_mut(edge.clone());
+                    subgraph.edges.insert_mut(edge.clone(), value.clone());
+                }
+            }
+        }
+        subgraph
+    }
+}
+
+impl<V, E> Union<V, E> for PersistentGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn union(&self, other: &Self, policy: ConflictPolicy) -> (Self, BTreeSet<Conflict<V, E>>) {
+        let (graph, conflicts) = BTreeGraph::from(self).union(&BTreeGraph::from(other), policy);
+        (PersistentGraph::from(graph), conflicts)
+    }
+}
+
+impl<V, E> Intersection<V, E> for PersistentGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn intersection(
+        &self,
+        other: &Self,
+        policy: ConflictPolicy,
+    ) -> (Self, BTreeSet<Conflict<V, E>>) {
+        let (graph, conflicts) =
+            BTreeGraph::from(self).intersection(&BTreeGraph::from(other), policy);
+        (PersistentGraph::from(graph), conflicts)
+    }
+}
+
+impl<V, E> Difference<V, E> for PersistentGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn difference(&self, other: &Self, policy: ConflictPolicy) -> (Self, BTreeSet<Conflict<V, E>>) {
+        let (graph, conflicts) =
+            BTreeGraph::from(self).difference(&BTreeGraph::from(other), policy);
+        (PersistentGraph::from(graph), conflicts)
+    }
+}
+
+impl<V, E> SymmetricDifference<V, E> for PersistentGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn symmetric_difference(
+        &self,
+        other: &Self,
+        policy: ConflictPolicy,
+    ) -> (Self, BTreeSet<Conflict<V, E>>) {
+        let (graph, conflicts) =
+            BTreeGraph::from(self).symmetric_difference(&BTreeGraph::from(other), policy);
+        (PersistentGraph::from(graph), conflicts)
+    }
+}
+
+impl<V, E> Maps<V, E> for PersistentGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn contains_vertex(&self, v: &V) -> bool {
+        self.vertices.contains_key(v)
+    }
+
+    fn vertex_keys<'a>(&'a self) -> impl Iterator<Item = &'a V>
+    where
+        V: 'a,
+    {
+        self.vertices.keys()
+    }
+
+    fn edge_value(&self, e: &E) -> Option<&(V, V)> {
+        self.edges.get(e)
+    }
+
+    fn edge_entries<'a>(&'a self) -> impl Iterator<Item = (&'a E, &'a (V, V))>
+    where
+        V: 'a,
+        E: 'a,
+    {
+        self.edges.iter()
+    }
+}
+
+impl<V, E> Diff<V, E> for PersistentGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn diff(&self, other: &Self) -> GraphDiff<V, E> {
+        diff::diff(self, other)
+    }
+}
+
+/// Every change is checked against the graph before any is made, so a diff which does not
+/// apply leaves the graph untouched.
+impl<V, E> ApplyDiff<V, E> for PersistentGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    type Error = Error;
+    fn apply(&mut self, diff: &GraphDiff<V, E>) -> Result<(), Self::Error> {
+        diff::check(self, diff)?;
+
+        // Every check has passed, so no change below can fail.
+        for e in diff.removed_edges.keys().chain(diff.changed_edges.keys()) {
+            self.remove_edge(e.clone())?;
+        }
+        for v in diff.removed_vertices.iter() {
+            // Any edge still incident to the vertex is cascade deleted.
+            self.remove_vertex(v.clone())?;
+        }
+        for v in diff.added_vertices.iter() {
+            self.add_vertex(v.clone());
+        }
+        let new_edges = diff
+            .added_edges
+            .iter()
+            .chain(diff.changed_edges.iter().map(|(e, (_, new))| (e, new)));
+        for (e, (x, y)) in new_edges {
+            self.add_edge(x.clone(), y.clone(), e.clone())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+//! An alternative serde representation of `BTreeGraph`, for hand-edited documents such as
+//! configuration files. The graph is written as a list of vertices, and a list of edges,
+//! each with its id, and the vertices it goes from, and to; in YAML:
+//!
+//! ```yaml
+//! vertices: [origin, destination]
+//! edges:
+//!   - { id: 10, from: origin, to: destination }
+//! ```
+//!
+//! Either list may be left out if it is empty. Deserializing raises an error if a vertex,
+//! or edge is listed more than once, or an edge goes from, or to a vertex which is not
+//! listed.
+//!
+//! # Example
+//!
+//! ```
+//! use btree_graph::BTreeGraph;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "btree_graph::readable")]
+//!     routes: BTreeGraph<String, usize>,
+//! }
+//! ```
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::BTreeGraph;
+
+#[derive(Serialize)]
+#[serde(rename = "BTreeGraph")]
+struct ReadableRef<'a, V, E> {
+    vertices: Vec<&'a V>,
+    edges: Vec<ReadableEdgeRef<'a, V, E>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename = "Edge")]
+struct ReadableEdgeRef<'a, V, E> {
+    id: &'a E,
+    from: &'a V,
+    to: &'a V,
+}
+
+#[derive(Deserialize)]
+#[serde(
+    rename = "BTreeGraph",
+    deny_unknown_fields,
+    bound(deserialize = "V: Deserialize<'de>, E: Deserialize<'de>")
+)]
+struct Readable<V, E> {
+    #[serde(default)]
+    vertices: Vec<V>,
+    #[serde(default)]
+    edges: Vec<ReadableEdge<V, E>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Edge", deny_unknown_fields)]
+struct ReadableEdge<V, E> {
+    id: E,
+    from: V,
+    to: V,
+}
+
+/// Serializes the graph as a list of vertices, and a list of edges.
+pub fn serialize<V, E, S>(graph: &BTreeGraph<V, E>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    V: Ord + Serialize,
+    E: Ord + Serialize,
+    S: Serializer,
+{
+    ReadableRef {
+        vertices: graph.vertices.keys().collect(),
+        edges: graph
+            .edges
+            .iter()
+            .map(|(id, (from, to))| ReadableEdgeRef { id, from, to })
+            .collect(),
+    }
+    .serialize(serializer)
+}
+
+/// Deserializes a graph from a list of vertices, and a list of edges.
+pub fn deserialize<'de, V, E, D>(deserializer: D) -> Result<BTreeGraph<V, E>, D::Error>
+where
+    V: Ord + Deserialize<'de>,
+    E: Ord + Clone + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    let readable: Readable<V, E> = Readable::deserialize(deserializer)?;
+    let mut vertices: BTreeMap<V, BTreeSet<E>> = BTreeMap::new();
+    for v in readable.vertices {
+        if vertices.insert(v, BTreeSet::new()).is_some() {
+            return Err(D::Error::custom(
+                "invalid graph: a vertex is listed more than once",
+            ));
+        }
+    }
+    let mut edges: BTreeMap<E, (V, V)> = BTreeMap::new();
+    for edge in readable.edges {
+        if !vertices.contains_key(&edge.to) {
+            return Err(D::Error::custom(
+                "invalid graph: an edge goes to a vertex which is not listed",
+            ));
+        }
+        match vertices.get_mut(&edge.from) {
+            Some(adjacent) => {
+                adjacent.insert(edge.id.clone());
+            }
+            None => {
+                return Err(D::Error::custom(
+                    "invalid graph: an edge goes from a vertex which is not listed",
+                ))
+            }
+        }
+        if edges.insert(edge.id, (edge.from, edge.to)).is_some() {
+            return Err(D::Error::custom(
+                "invalid graph: an edge is listed more than once",
+            ));
+        }
+    }
+    Ok(BTreeGraph { vertices, edges })
+}
@@ -0,0 +1,155 @@
+use alloc::collections::BTreeSet;
+
+use crate::{BTreeGraph, Difference, Intersection, SymmetricDifference, Union};
+
+/// `Conflict` is an edge key which maps to different vertices in the two graphs given to
+/// a set operation, along with the value of the edge in each graph (left, right).
+pub type Conflict<V, E> = (E, ((V, V), (V, V)));
+
+/// `ConflictPolicy` decides which value of a conflicting edge, if any, is used by a set
+/// operation.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ConflictPolicy {
+    /// Use the value from the graph the operation was called on.
+    Left,
+    /// Use the value from the graph passed as an argument.
+    Right,
+    /// Leave the edge out of the result.
+    Exclude,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum SetOperation {
+    Union,
+    Intersection,
+    Difference,
+    SymmetricDifference,
+}
+
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    /// Combines the vertex and edge maps of both graphs by key. Conflicting edges are
+    /// resolved by the policy and reported.
+    fn combine(
+        &self,
+        other: &Self,
+        policy: ConflictPolicy,
+        op: SetOperation,
+    ) -> (Self, BTreeSet<Conflict<V, E>>) {
+        let keep = |left: bool, right: bool| -> bool {
+            match op {
+                SetOperation::Union => left || right,
+                SetOperation::Intersection => left && right,
+                SetOperation::Difference => left && !right,
+                SetOperation::SymmetricDifference => left != right,
+            }
+        };
+
+        let mut graph: BTreeGraph<V, E> = BTreeGraph::new();
+        let vertices = self.vertices.keys().chain(
+            other
+                .vertices
+                .keys()
+                .filter(|v| !self.vertices.contains_key(v)),
+        );
+        for v in vertices {
+            if keep(
+                self.vertices.contains_key(v),
+                other.vertices.contains_key(v),
+            ) {
+                graph.vertices.insert(v.clone(), BTreeSet::new());
+            }
+        }
+
+        let mut conflicts: BTreeSet<Conflict<V, E>> = BTreeSet::new();
+        let edges = self
+            .edges
+            .keys()
+            .chain(other.edges.keys().filter(|e| !self.edges.contains_key(e)));
+        for e in edges {
+            let value = match (self.edges.get(e), other.edges.get(e)) {
+                (Some(left), Some(right)) if left != right => {
+                    conflicts.insert((e.clone(), (left.clone(), right.clone())));
+                    match (policy, op) {
+                        (ConflictPolicy::Exclude, _) => None,
+                        (ConflictPolicy::Left, _) => Some(left),
+                        // The right value of the edge can never be a part of
+                        // the difference.
+                        (ConflictPolicy::Right, SetOperation::Difference) => None,
+                        (ConflictPolicy::Right, _) => Some(right),
+                    }
+                }
+                (left, right) if keep(left.is_some(), right.is_some()) => left.or(right),
+                _ => None,
+            };
+            if let Some((x, y)) = value {
+                // Edges which survive an intersection are only kept when both
+                // endpoints survive; otherwise the endpoints are brought along.
+                if op == SetOperation::Intersection
+                    && !(graph.vertices.contains_key(x) && graph.vertices.contains_key(y))
+                {
+                    continue;
+                }
+                graph.vertices.entry(y.clone()).or_default();
+                graph
+                    .vertices
+                    .entry(x.clone())
+                    .or_default()
+                    .insert(e.clone());
+                graph.edges.insert(e.clone(), (x.clone(), y.clone()));
+            }
+        }
+        (graph, conflicts)
+    }
+}
+
+impl<V, E> Union<V, E> for BTreeGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn union(&self, other: &Self, policy: ConflictPolicy) -> (Self, BTreeSet<Conflict<V, E>>) {
+        self.combine(other, policy, SetOperation::Union)
+    }
+}
+
+impl<V, E> Intersection<V, E> for BTreeGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn intersection(
+        &self,
+        other: &Self,
+        policy: ConflictPolicy,
+    ) -> (Self, BTreeSet<Conflict<V, E>>) {
+        self.combine(other, policy, SetOperation::Intersection)
+    }
+}
+
+impl<V, E> Difference<V, E> for BTreeGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn difference(&self, other: &Self, policy: ConflictPolicy) -> (Self, BTreeSet<Conflict<V, E>>) {
+        self.combine(other, policy, SetOperation::Difference)
+    }
+}
+
+impl<V, E> SymmetricDifference<V, E> for BTreeGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn symmetric_difference(
+        &self,
+        other: &Self,
+        policy: ConflictPolicy,
+    ) -> (Self, BTreeSet<Conflict<V, E>>) {
+        self.combine(other, policy, SetOperation::SymmetricDifference)
+    }
+}
@@ -0,0 +1,78 @@
+use alloc::collections::BTreeSet;
+
+use crate::{BTreeGraph, EdgeSubgraph, FilterMap, InducedSubgraph};
+
+impl<V, E> InducedSubgraph<V> for BTreeGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn induced_subgraph(&self, x: &BTreeSet<V>) -> Self {
+        let mut subgraph: BTreeGraph<V, E> = BTreeGraph::new();
+        for (v, adjacent) in x.iter().filter_map(|v| self.vertices.get_key_value(v)) {
+            let mut retained: BTreeSet<E> = BTreeSet::new();
+            for edge in adjacent {
+                // We can assume an edge exists if it is found adjacent
+                // to some vertex.
+                let value = self.edges.get(edge).unwrap();
+                if x.contains(&value.1) {
+                    retained.insert(edge.clone());
+                    subgraph.edges.insert(edge.clone(), value.clone());
+                }
+            }
+            subgraph.vertices.insert(v.clone(), retained);
+        }
+        subgraph
+    }
+}
+
+impl<V, E> EdgeSubgraph<E> for BTreeGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn edge_subgraph(&self, x: &BTreeSet<E>) -> Self {
+        let mut subgraph: BTreeGraph<V, E> = BTreeGraph::new();
+        for (edge, (from, to)) in x.iter().filter_map(|e| self.edges.get_key_value(e)) {
+            subgraph
+                .vertices
+                .entry(from.clone())
+                .or_default()
+                .insert(edge.clone());
+            subgraph.vertices.entry(to.clone()).or_default();
+            subgraph
+                .edges
+                .insert(edge.clone(), (from.clone(), to.clone()));
+        }
+        subgraph
+    }
+}
+
+impl<V, E> FilterMap<V, E> for BTreeGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn filter_map<P, Q>(&self, mut vertex_pred: P, mut edge_pred: Q) -> Self
+    where
+        P: FnMut(&V) -> bool,
+        Q: FnMut(&E, &(V, V)) -> bool,
+    {
+        let mut subgraph: BTreeGraph<V, E> = BTreeGraph::new();
+        for v in self.vertices.keys().filter(|v| vertex_pred(v)) {
+            subgraph.vertices.insert(v.clone(), BTreeSet::new());
+        }
+        for (edge, value) in self.edges.iter() {
+            if !subgraph.vertices.contains_key(&value.1) {
+                continue;
+            }
+            if let Some(adjacent) = subgraph.vertices.get_mut(&value.0) {
+                if edge_pred(edge, value) {
+                    adjacent.insert(edge.clone());
+                    subgraph.edges.insert(edge.clone(), value.clone());
+                }
+            }
+        }
+        subgraph
+    }
+}
@@ -4,6 +4,9 @@ mod unit_tests {
     use crate::Error;
     use crate::graph::*;
     use alloc::collections::{BTreeMap, BTreeSet};
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
 
     #[test]
     fn test_graph() {
@@ -374,4 +377,2130 @@ mod unit_tests {
         // Test passed.
         Ok(())
     }
+
+    #[test]
+    fn sources_sinks_and_isolated_vertices() -> Result<(), Error> {
+        // Add five nodes.
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        graph.add_vertex(0);
+        graph.add_vertex(1);
+        graph.add_vertex(2);
+        graph.add_vertex(3);
+        graph.add_vertex(4);
+
+        // Add an edge (0, 1) = 2, (1, 2) = 3, and a self-loop (4, 4) = 4.
+        graph.add_edge(0, 1, 2)?;
+        graph.add_edge(1, 2, 3)?;
+        graph.add_edge(4, 4, 4)?;
+
+        // Vertex 0 has no incoming edges, and vertex 3 has no edges at all.
+        let sources: Vec<&usize> = graph.sources().collect();
+        assert_eq!(sources, vec![&0, &3]);
+
+        // Vertex 2 has no outgoing edges, and vertex 3 has no edges at all.
+        let sinks: Vec<&usize> = graph.sinks().collect();
+        assert_eq!(sinks, vec![&2, &3]);
+
+        // Only vertex 3 is isolated; the self-loop on 4 is both an in and out edge.
+        let isolated: Vec<&usize> = graph.isolated_vertices().collect();
+        assert_eq!(isolated, vec![&3]);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn remove_isolated_vertices() -> Result<(), Error> {
+        // Add four nodes.
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        graph.add_vertex(0);
+        graph.add_vertex(1);
+        graph.add_vertex(2);
+        graph.add_vertex(3);
+
+        // Add an edge (0, 1) = 2.
+        graph.add_edge(0, 1, 2)?;
+
+        // Vertices 2, and 3 are removed.
+        let mut exp_removed: BTreeSet<usize> = BTreeSet::new();
+        exp_removed.insert(2);
+        exp_removed.insert(3);
+        assert_eq!(graph.remove_isolated_vertices(), exp_removed);
+
+        // Vertices 0, and 1 remain along with their edge.
+        let mut exp_vertices: BTreeSet<&usize> = BTreeSet::new();
+        exp_vertices.insert(&0);
+        exp_vertices.insert(&1);
+        assert_eq!(graph.vertices(), exp_vertices);
+        assert_eq!(graph.get_edge_value(2).unwrap(), &(0, 1));
+
+        // Removing again is a no-op.
+        assert!(graph.remove_isolated_vertices().is_empty());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn induced_subgraph() -> Result<(), Error> {
+        // Add three nodes.
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        graph.add_vertex(0);
+        graph.add_vertex(1);
+        graph.add_vertex(2);
+
+        // Add an edge (0, 1) = 2, (1, 2) = 3, and (1, 1) = 4.
+        graph.add_edge(0, 1, 2)?;
+        graph.add_edge(1, 2, 3)?;
+        graph.add_edge(1, 1, 4)?;
+
+        // Induce on vertices 1, 2 and the non-existent vertex 3.
+        let mut x: BTreeSet<usize> = BTreeSet::new();
+        x.insert(1);
+        x.insert(2);
+        x.insert(3);
+        let subgraph = graph.induced_subgraph(&x);
+
+        // Only the existing vertices are retained.
+        let mut exp_vertices: BTreeSet<&usize> = BTreeSet::new();
+        exp_vertices.insert(&1);
+        exp_vertices.insert(&2);
+        assert_eq!(subgraph.vertices(), exp_vertices);
+
+        // Edge 2 is dropped since vertex 0 was not chosen.
+        let mut exp_edges: BTreeSet<&usize> = BTreeSet::new();
+        exp_edges.insert(&3);
+        exp_edges.insert(&4);
+        assert_eq!(subgraph.edges(), exp_edges);
+
+        let mut exp_edges_1: BTreeSet<usize> = BTreeSet::new();
+        exp_edges_1.insert(3);
+        exp_edges_1.insert(4);
+        assert_eq!(subgraph.get_vertex_value(1).unwrap(), &exp_edges_1);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn edge_subgraph() -> Result<(), Error> {
+        // Add three nodes.
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        graph.add_vertex(0);
+        graph.add_vertex(1);
+        graph.add_vertex(2);
+
+        // Add an edge (0, 1) = 2, and (1, 2) = 3.
+        graph.add_edge(0, 1, 2)?;
+        graph.add_edge(1, 2, 3)?;
+
+        // Keep edge 3 and the non-existent edge 5.
+        let mut x: BTreeSet<usize> = BTreeSet::new();
+        x.insert(3);
+        x.insert(5);
+        let subgraph = graph.edge_subgraph(&x);
+
+        // Both endpoints of edge 3 are retained.
+        let mut exp_vertices: BTreeSet<&usize> = BTreeSet::new();
+        exp_vertices.insert(&1);
+        exp_vertices.insert(&2);
+        assert_eq!(subgraph.vertices(), exp_vertices);
+        assert_eq!(subgraph.get_edge_value(3).unwrap(), &(1, 2));
+        assert_eq!(subgraph.edges().len(), 1);
+        assert!(subgraph.get_vertex_value(2).unwrap().is_empty());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn filter_map() -> Result<(), Error> {
+        // Add three nodes.
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        graph.add_vertex(0);
+        graph.add_vertex(1);
+        graph.add_vertex(2);
+
+        // Add an edge (0, 1) = 2, (1, 2) = 3, and (0, 2) = 4.
+        graph.add_edge(0, 1, 2)?;
+        graph.add_edge(1, 2, 3)?;
+        graph.add_edge(0, 2, 4)?;
+
+        // Accepting everything yields an identical graph.
+        assert_eq!(graph.filter_map(|_| true, |_, _| true), graph);
+
+        // Rejecting vertex 1 drops edges 2 and 3, rejecting edge 4 drops it too.
+        let subgraph = graph.filter_map(|v| *v != 1, |e, _| *e != 4);
+        let mut exp_vertices: BTreeSet<&usize> = BTreeSet::new();
+        exp_vertices.insert(&0);
+        exp_vertices.insert(&2);
+        assert_eq!(subgraph.vertices(), exp_vertices);
+        assert!(subgraph.edges().is_empty());
+        assert!(subgraph.get_vertex_value(0).unwrap().is_empty());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn reversed_view() -> Result<(), Error> {
+        // Add three nodes.
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        graph.add_vertex(0);
+        graph.add_vertex(1);
+        graph.add_vertex(2);
+
+        // Add an edge (0, 1) = 2, (1, 2) = 3, and (0, 2) = 4.
+        graph.add_edge(0, 1, 2)?;
+        graph.add_edge(1, 2, 3)?;
+        graph.add_edge(0, 2, 4)?;
+
+        let reversed = Reversed::new(&graph);
+
+        // Vertices, and edges are unchanged.
+        assert_eq!(reversed.vertices(), graph.vertices());
+        assert_eq!(reversed.edges(), graph.edges());
+
+        // The direction of every edge is flipped.
+        assert_eq!(reversed.get_edge_value(2), Some(&(1, 0)));
+        assert_eq!(reversed.get_edge_value(4), Some(&(2, 0)));
+        assert!(reversed.get_edge_value(5).is_none());
+        assert!(reversed.adjacent(1, 0)?);
+        assert!(!reversed.adjacent(0, 1)?);
+
+        // Vertex 2 is now 'connected' to vertices 0, and 1.
+        let mut exp_connections_2: BTreeSet<&usize> = BTreeSet::new();
+        exp_connections_2.insert(&0);
+        exp_connections_2.insert(&1);
+        assert_eq!(reversed.connections(2)?, exp_connections_2);
+        assert!(reversed.connections(0)?.is_empty());
+        assert_eq!(
+            reversed.connections(3).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn filtered_view() -> Result<(), Error> {
+        // Add three nodes.
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        graph.add_vertex(0);
+        graph.add_vertex(1);
+        graph.add_vertex(2);
+
+        // Add an edge (0, 1) = 2, (1, 2) = 3, and (0, 2) = 4.
+        graph.add_edge(0, 1, 2)?;
+        graph.add_edge(1, 2, 3)?;
+        graph.add_edge(0, 2, 4)?;
+
+        // Hide vertex 1, and edge 4.
+        let filtered = Filtered::new(
+            &graph,
+            |v: &usize| *v != 1,
+            |e: &usize, _: &(usize, usize)| *e != 4,
+        );
+
+        let mut exp_vertices: BTreeSet<&usize> = BTreeSet::new();
+        exp_vertices.insert(&0);
+        exp_vertices.insert(&2);
+        assert_eq!(filtered.vertices(), exp_vertices);
+        assert!(filtered.edges().is_empty());
+        assert!(filtered.get_edge_value(2).is_none());
+        assert!(filtered.get_edge_value(4).is_none());
+        assert!(!filtered.adjacent(0, 2)?);
+        assert!(filtered.connections(0)?.is_empty());
+
+        // Hidden vertices are treated as if they do not exist.
+        assert_eq!(
+            filtered.adjacent(0, 1).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+        assert_eq!(
+            filtered.connections(1).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn subgraph_view() -> Result<(), Error> {
+        // Add three nodes.
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        graph.add_vertex(0);
+        graph.add_vertex(1);
+        graph.add_vertex(2);
+
+        // Add an edge (0, 1) = 2, (1, 2) = 3, and (0, 2) = 4.
+        graph.add_edge(0, 1, 2)?;
+        graph.add_edge(1, 2, 3)?;
+        graph.add_edge(0, 2, 4)?;
+
+        // View the subgraph induced by vertices 0, and 2.
+        let mut x: BTreeSet<usize> = BTreeSet::new();
+        x.insert(0);
+        x.insert(2);
+        let view = SubgraphView::new(&graph, &x);
+
+        // The view agrees with the materialized induced subgraph.
+        let subgraph = graph.induced_subgraph(&x);
+        assert_eq!(view.vertices(), subgraph.vertices());
+        assert_eq!(view.edges(), subgraph.edges());
+        assert_eq!(view.get_edge_value(4), Some(&(0, 2)));
+        assert!(view.get_edge_value(2).is_none());
+        assert!(view.adjacent(0, 2)?);
+        assert_eq!(view.connections(0)?, subgraph.connections(0)?);
+        assert_eq!(view.connections(1).unwrap_err(), Error::VertexDoesNotExist);
+
+        // Test passed.
+        Ok(())
+    }
+
+    fn triangle() -> Result<BTreeGraph<usize, usize>, Error> {
+        // Add vertices 0, 1, 2 and edges (0, 1) = 2, (1, 2) = 3, (0, 2) = 4.
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        graph.add_vertex(0);
+        graph.add_vertex(1);
+        graph.add_vertex(2);
+        graph.add_edge(0, 1, 2)?;
+        graph.add_edge(1, 2, 3)?;
+        graph.add_edge(0, 2, 4)?;
+        Ok(graph)
+    }
+
+    fn shifted_triangle() -> Result<BTreeGraph<usize, usize>, Error> {
+        // Add vertices 1, 2, 3 and edges (1, 2) = 3, (2, 3) = 4, (3, 1) = 5; edge 4
+        // conflicts with the triangle.
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        graph.add_vertex(1);
+        graph.add_vertex(2);
+        graph.add_vertex(3);
+        graph.add_edge(1, 2, 3)?;
+        graph.add_edge(2, 3, 4)?;
+        graph.add_edge(3, 1, 5)?;
+        Ok(graph)
+    }
+
+    #[test]
+    fn union() -> Result<(), Error> {
+        let left = triangle()?;
+        let right = shifted_triangle()?;
+
+        let mut exp_conflicts: BTreeSet<Conflict<usize, usize>> = BTreeSet::new();
+        exp_conflicts.insert((4, ((0, 2), (2, 3))));
+
+        // Every vertex, and edge is in the union.
+        let (union, conflicts) = left.union(&right, ConflictPolicy::Left);
+        assert_eq!(conflicts, exp_conflicts);
+        assert_eq!(union.vertices().len(), 4);
+        assert_eq!(union.edges().len(), 4);
+        assert_eq!(union.get_edge_value(4).unwrap(), &(0, 2));
+        assert!(union.get_vertex_value(0).unwrap().contains(&4));
+
+        // The policy picks the value of the conflicting edge.
+        let (union, conflicts) = left.union(&right, ConflictPolicy::Right);
+        assert_eq!(conflicts, exp_conflicts);
+        assert_eq!(union.get_edge_value(4).unwrap(), &(2, 3));
+        assert!(!union.get_vertex_value(0).unwrap().contains(&4));
+        assert!(union.get_vertex_value(2).unwrap().contains(&4));
+
+        let (union, _) = left.union(&right, ConflictPolicy::Exclude);
+        assert!(union.get_edge_value(4).is_none());
+        assert_eq!(union.edges().len(), 3);
+
+        // The union of a graph with itself is the graph.
+        let (union, conflicts) = left.union(&left, ConflictPolicy::Exclude);
+        assert_eq!(union, left);
+        assert!(conflicts.is_empty());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn intersection() -> Result<(), Error> {
+        let left = triangle()?;
+        let right = shifted_triangle()?;
+
+        // Only vertices 1, 2 and edge 3 are in both graphs.
+        let (intersection, conflicts) = left.intersection(&right, ConflictPolicy::Exclude);
+        assert_eq!(conflicts.len(), 1);
+        let mut exp_vertices: BTreeSet<&usize> = BTreeSet::new();
+        exp_vertices.insert(&1);
+        exp_vertices.insert(&2);
+        assert_eq!(intersection.vertices(), exp_vertices);
+        let mut exp_edges: BTreeSet<&usize> = BTreeSet::new();
+        exp_edges.insert(&3);
+        assert_eq!(intersection.edges(), exp_edges);
+
+        // Neither value of the conflicting edge has both endpoints in the intersection.
+        let (intersection, _) = left.intersection(&right, ConflictPolicy::Left);
+        assert_eq!(intersection.edges(), exp_edges);
+        let (intersection, _) = left.intersection(&right, ConflictPolicy::Right);
+        assert_eq!(intersection.edges(), exp_edges);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn difference() -> Result<(), Error> {
+        let left = triangle()?;
+        let right = shifted_triangle()?;
+
+        // Vertex 0 and edge 2 are only in the left graph; vertex 1 is an endpoint of edge 2.
+        let (difference, conflicts) = left.difference(&right, ConflictPolicy::Exclude);
+        assert_eq!(conflicts.len(), 1);
+        let mut exp_vertices: BTreeSet<&usize> = BTreeSet::new();
+        exp_vertices.insert(&0);
+        exp_vertices.insert(&1);
+        assert_eq!(difference.vertices(), exp_vertices);
+        let mut exp_edges: BTreeSet<&usize> = BTreeSet::new();
+        exp_edges.insert(&2);
+        assert_eq!(difference.edges(), exp_edges);
+
+        // The right value of a conflict is never a part of the difference.
+        let (difference, _) = left.difference(&right, ConflictPolicy::Right);
+        assert_eq!(difference.edges(), exp_edges);
+
+        // The left value of a conflict brings along its endpoints.
+        let (difference, _) = left.difference(&right, ConflictPolicy::Left);
+        exp_edges.insert(&4);
+        assert_eq!(difference.edges(), exp_edges);
+        assert_eq!(difference.get_edge_value(4).unwrap(), &(0, 2));
+        assert_eq!(difference.vertices().len(), 3);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn symmetric_difference() -> Result<(), Error> {
+        let left = triangle()?;
+        let right = shifted_triangle()?;
+
+        // Vertices 0, 3 and edges 2, 5 are in exactly one graph.
+        let (symmetric_difference, conflicts) =
+            left.symmetric_difference(&right, ConflictPolicy::Exclude);
+        assert_eq!(conflicts.len(), 1);
+        let mut exp_edges: BTreeSet<&usize> = BTreeSet::new();
+        exp_edges.insert(&2);
+        exp_edges.insert(&5);
+        assert_eq!(symmetric_difference.edges(), exp_edges);
+        // Vertex 1 is brought along as an endpoint of edges 2, and 5.
+        let mut exp_vertices: BTreeSet<&usize> = BTreeSet::new();
+        exp_vertices.insert(&0);
+        exp_vertices.insert(&1);
+        exp_vertices.insert(&3);
+        assert_eq!(symmetric_difference.vertices(), exp_vertices);
+
+        // Either value of a conflict may be kept.
+        let (symmetric_difference, _) = left.symmetric_difference(&right, ConflictPolicy::Right);
+        assert_eq!(symmetric_difference.get_edge_value(4).unwrap(), &(2, 3));
+        assert!(symmetric_difference
+            .get_vertex_value(2)
+            .unwrap()
+            .contains(&4));
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn diff() -> Result<(), Error> {
+        let before = triangle()?;
+        let mut after = triangle()?;
+
+        // Add vertex 3, remove vertex 1 (and with it edges 2, and 3), and point edge 4
+        // at vertex 3 instead.
+        after.add_vertex(3);
+        after.remove_vertex(1)?;
+        after.remove_edge(4)?;
+        after.add_edge(0, 3, 4)?;
+        after.add_edge(3, 2, 5)?;
+
+        let diff = before.diff(&after);
+        let mut exp_vertices: BTreeSet<usize> = BTreeSet::new();
+        exp_vertices.insert(3);
+        assert_eq!(diff.added_vertices, exp_vertices);
+        let mut exp_vertices: BTreeSet<usize> = BTreeSet::new();
+        exp_vertices.insert(1);
+        assert_eq!(diff.removed_vertices, exp_vertices);
+        let mut exp_edges: BTreeMap<usize, (usize, usize)> = BTreeMap::new();
+        exp_edges.insert(5, (3, 2));
+        assert_eq!(diff.added_edges, exp_edges);
+        let mut exp_edges: BTreeMap<usize, (usize, usize)> = BTreeMap::new();
+        exp_edges.insert(2, (0, 1));
+        exp_edges.insert(3, (1, 2));
+        assert_eq!(diff.removed_edges, exp_edges);
+        let mut exp_changes: BTreeMap<usize, EdgeChange<usize>> = BTreeMap::new();
+        exp_changes.insert(4, ((0, 2), (0, 3)));
+        assert_eq!(diff.changed_edges, exp_changes);
+
+        // The diff of a graph with itself is empty.
+        assert!(before.diff(&before).is_empty());
+        assert_eq!(before.diff(&before), GraphDiff::default());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn apply_diff() -> Result<(), Error> {
+        let before = triangle()?;
+        let after = shifted_triangle()?;
+
+        // Applying the diff of two graphs to the first yields the second.
+        let mut graph = before.clone();
+        graph.apply(&before.diff(&after))?;
+        assert_eq!(graph, after);
+
+        // Applying the reverse diff yields the first again.
+        graph.apply(&after.diff(&before))?;
+        assert_eq!(graph, before);
+
+        // A diff which does not apply leaves the graph untouched.
+        let mut diff = before.diff(&after);
+        diff.added_vertices.insert(0);
+        assert_eq!(graph.apply(&diff).unwrap_err(), Error::VertexExists);
+        assert_eq!(graph, before);
+
+        let mut diff: GraphDiff<usize, usize> = GraphDiff::new();
+        diff.removed_vertices.insert(5);
+        assert_eq!(graph.apply(&diff).unwrap_err(), Error::VertexDoesNotExist);
+
+        let mut diff: GraphDiff<usize, usize> = GraphDiff::new();
+        diff.removed_edges.insert(2, (1, 0));
+        assert_eq!(graph.apply(&diff).unwrap_err(), Error::EdgeDoesNotExist);
+
+        let mut diff: GraphDiff<usize, usize> = GraphDiff::new();
+        diff.changed_edges.insert(4, ((0, 1), (0, 2)));
+        assert_eq!(graph.apply(&diff).unwrap_err(), Error::EdgeDoesNotExist);
+
+        let mut diff: GraphDiff<usize, usize> = GraphDiff::new();
+        diff.added_edges.insert(2, (0, 1));
+        assert_eq!(graph.apply(&diff).unwrap_err(), Error::EdgeExists);
+
+        let mut diff: GraphDiff<usize, usize> = GraphDiff::new();
+        diff.added_edges.insert(5, (0, 3));
+        assert_eq!(graph.apply(&diff).unwrap_err(), Error::VertexDoesNotExist);
+
+        let mut diff: GraphDiff<usize, usize> = GraphDiff::new();
+        diff.removed_vertices.insert(0);
+        diff.added_edges.insert(5, (0, 1));
+        assert_eq!(graph.apply(&diff).unwrap_err(), Error::VertexDoesNotExist);
+        assert_eq!(graph, before);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn transaction_commit() -> Result<(), Error> {
+        let mut graph = triangle()?;
+        let mut exp_graph = triangle()?;
+
+        // Every mutation made by a successful transaction is kept.
+        let removed = graph.transaction(|tx| -> Result<usize, Error> {
+            tx.add_vertex(3);
+            tx.add_edge(2, 3, 5)?;
+            tx.remove_edge(2)?;
+            Ok(tx.remove_vertex(1)?.len())
+        })?;
+
+        exp_graph.add_vertex(3);
+        exp_graph.add_edge(2, 3, 5)?;
+        exp_graph.remove_edge(2)?;
+        assert_eq!(exp_graph.remove_vertex(1)?.len(), removed);
+        assert_eq!(graph, exp_graph);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn transaction_rollback() -> Result<(), Error> {
+        let mut graph = triangle()?;
+        let before = graph.clone();
+
+        // Every mutation made by a failed transaction is rolled back.
+        let result = graph.transaction(|tx| -> Result<(), Error> {
+            tx.add_vertex(3);
+            tx.add_edge(3, 0, 5)?;
+            // Re-adding an existing vertex leaves its adjacency list untouched.
+            assert!(!tx.add_vertex(0));
+            assert_eq!(tx.connections(0)?.len(), 2);
+            // Overwrite an existing edge with a new value.
+            tx.add_edge(1, 0, 4)?;
+            tx.remove_edge(3)?;
+            // Remove a vertex with a self-loop, and incoming, and outgoing edges.
+            tx.add_edge(2, 2, 6)?;
+            tx.remove_vertex(2)?;
+            assert_eq!(tx.vertices().len(), 3);
+            tx.remove_edge(7)?;
+            Ok(())
+        });
+        assert_eq!(result.unwrap_err(), Error::EdgeDoesNotExist);
+        assert_eq!(graph, before);
+
+        // The error type of the transaction is up to the caller.
+        let result: Result<(), &str> = graph.transaction(|tx| {
+            tx.remove_vertex(0).map_err(|_| "unreachable")?;
+            Err("abort")
+        });
+        assert_eq!(result.unwrap_err(), "abort");
+        assert_eq!(graph, before);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn journal_undo_redo() -> Result<(), Error> {
+        let before = triangle()?;
+        let mut graph = Journaled::new(before.clone());
+
+        // Make one of each mutation.
+        graph.add_vertex(3);
+        graph.add_edge(2, 3, 5)?;
+        graph.remove_edge(2)?;
+        graph.remove_vertex(1)?;
+        let after = graph.graph().clone();
+
+        // Failed mutations are not journaled.
+        assert_eq!(
+            graph.add_edge(1, 3, 6).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+        assert_eq!(graph.remove_edge(2).unwrap_err(), Error::EdgeDoesNotExist);
+
+        // Neither is re-adding an existing vertex, so undo reverts the last removal.
+        assert!(!graph.add_vertex(3));
+        assert!(graph.undo());
+        assert!(graph.graph().vertices().contains(&1));
+        assert!(graph.redo()?);
+        assert_eq!(graph.graph(), &after);
+
+        // Undo every mutation.
+        while graph.undo() {}
+        assert_eq!(graph.graph(), &before);
+
+        // Redo every mutation.
+        while graph.redo()? {}
+        assert_eq!(graph.graph(), &after);
+
+        // A new mutation discards what could be redone.
+        assert!(graph.undo());
+        graph.add_vertex(4);
+        assert!(!graph.redo()?);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn journal_drain() -> Result<(), Error> {
+        let mut graph = Journaled::new(triangle()?);
+        graph.add_vertex(3);
+        graph.add_edge(2, 3, 5)?;
+        graph.remove_vertex(0)?;
+        graph.remove_edge(3)?;
+        assert!(graph.undo());
+
+        // Undone mutations are not drained.
+        let exp_operations: Vec<Operation<usize, usize>> = vec![
+            Operation::AddVertex(3),
+            Operation::AddEdge(2, 3, 5),
+            Operation::RemoveVertex(0),
+        ];
+        let operations = graph.drain();
+        assert_eq!(operations, exp_operations);
+
+        // Drained mutations can no longer be undone.
+        assert!(!graph.undo());
+        assert!(graph.drain().is_empty());
+
+        // Replaying the mutations replicates the graph.
+        let mut replica = triangle()?;
+        for op in operations {
+            op.apply(&mut replica)?;
+        }
+        assert_eq!(&replica, graph.graph());
+        assert_eq!(replica, graph.into_inner());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "persistent")]
+    fn persistent_graph() -> Result<(), Error> {
+        // Make the same mutations to both implementations.
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        let mut persistent: PersistentGraph<usize, usize> = PersistentGraph::new();
+        for v in 0..4 {
+            assert_eq!(graph.add_vertex(v), persistent.add_vertex(v));
+        }
+        assert_eq!(graph.add_edge(0, 1, 2)?, persistent.add_edge(0, 1, 2)?);
+        assert_eq!(graph.add_edge(1, 2, 3)?, persistent.add_edge(1, 2, 3)?);
+        assert_eq!(graph.add_edge(2, 0, 4)?, persistent.add_edge(2, 0, 4)?);
+        assert_eq!(graph.add_edge(3, 3, 5)?, persistent.add_edge(3, 3, 5)?);
+        assert_eq!(graph.add_edge(2, 1, 3)?, persistent.add_edge(2, 1, 3)?);
+        assert!(graph.validate().is_empty());
+        assert_eq!(graph.connections(1)?, BTreeSet::new());
+        assert_eq!(
+            graph.add_edge(0, 4, 6).unwrap_err(),
+            persistent.add_edge(0, 4, 6).unwrap_err()
+        );
+        assert_eq!(graph, BTreeGraph::from(&persistent));
+
+        // Reads agree.
+        assert_eq!(graph.vertices(), persistent.vertices());
+        assert_eq!(graph.edges(), persistent.edges());
+        assert_eq!(graph.get_edge_value(3), persistent.get_edge_value(3));
+        assert_eq!(
+            graph.get_vertex_value(2).cloned(),
+            persistent
+                .get_vertex_value(2)
+                .map(|edges| edges.iter().cloned().collect())
+        );
+        assert_eq!(graph.adjacent(2, 1)?, persistent.adjacent(2, 1)?);
+        assert_eq!(graph.adjacent(1, 2)?, persistent.adjacent(1, 2)?);
+        assert_eq!(graph.connections(2)?, persistent.connections(2)?);
+
+        // Snapshots are unaffected by later mutations.
+        let snapshot = persistent.snapshot();
+        assert_eq!(graph.remove_edge(2)?, persistent.remove_edge(2)?);
+        assert_eq!(graph.remove_vertex(0)?, persistent.remove_vertex(0)?);
+        assert_eq!(
+            graph.remove_vertex(0).unwrap_err(),
+            persistent.remove_vertex(0).unwrap_err()
+        );
+        assert_eq!(graph, BTreeGraph::from(&persistent));
+        assert_eq!(snapshot.vertices().len(), 4);
+        assert_eq!(snapshot.edges().len(), 4);
+        assert_ne!(snapshot, persistent);
+
+        // Converting from a BTreeGraph yields an equal graph.
+        assert_eq!(PersistentGraph::from(graph), persistent);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "persistent")]
+    fn persistent_graph_high_degree_snapshot() -> Result<(), Error> {
+        // Give vertex 0 an edge to each of 1000 other vertices.
+        let mut graph: PersistentGraph<usize, usize> = PersistentGraph::new();
+        for v in 0..=1000 {
+            graph.add_vertex(v);
+        }
+        for v in 1..=1000 {
+            graph.add_edge(0, v, v)?;
+        }
+
+        // Mutate the adjacency list of vertex 0 after taking a snapshot.
+        let snapshot = graph.snapshot();
+        graph.add_edge(0, 0, 0)?;
+        graph.remove_edge(500)?;
+        graph.add_edge(1, 2, 1)?;
+
+        let edges = graph.get_vertex_value(0).unwrap();
+        assert_eq!(edges.size(), 999);
+        assert!(edges.contains(&0));
+        assert!(!edges.contains(&500));
+        assert!(!edges.contains(&1));
+        assert_eq!(graph.connections(1)?.len(), 1);
+
+        // The snapshot still holds the original adjacency list.
+        let edges = snapshot.get_vertex_value(0).unwrap();
+        assert_eq!(edges.size(), 1000);
+        assert!(!edges.contains(&0));
+        assert!(edges.contains(&500));
+        assert!(edges.contains(&1));
+        assert!(snapshot.connections(1)?.is_empty());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "persistent")]
+    fn persistent_graph_queries() -> Result<(), Error> {
+        // Compare against the triangle, with an isolated vertex 3.
+        let mut graph = triangle()?;
+        graph.add_vertex(3);
+        let mut persistent = PersistentGraph::from(graph.clone());
+
+        assert!(graph.sources().eq(persistent.sources()));
+        assert!(graph.sinks().eq(persistent.sinks()));
+        assert!(graph.isolated_vertices().eq(persistent.isolated_vertices()));
+
+        let vertices: BTreeSet<usize> = [0, 2].iter().cloned().collect();
+        assert_eq!(
+            graph.induced_subgraph(&vertices),
+            BTreeGraph::from(&persistent.induced_subgraph(&vertices))
+        );
+        let edges: BTreeSet<usize> = [3, 4].iter().cloned().collect();
+        assert_eq!(
+            graph.edge_subgraph(&edges),
+            BTreeGraph::from(&persistent.edge_subgraph(&edges))
+        );
+        assert_eq!(
+            graph.filter_map(|v| *v != 1, |e, _| *e != 4),
+            BTreeGraph::from(&persistent.filter_map(|v| *v != 1, |e, _| *e != 4))
+        );
+
+        // Set operations agree, conflicts included.
+        let right = shifted_triangle()?;
+        let persistent_right = PersistentGraph::from(right.clone());
+        for policy in [
+            ConflictPolicy::Left,
+            ConflictPolicy::Right,
+            ConflictPolicy::Exclude,
+        ] {
+            let (union, conflicts) = graph.union(&right, policy);
+            let (p_union, p_conflicts) = persistent.union(&persistent_right, policy);
+            assert_eq!(union, BTreeGraph::from(&p_union));
+            assert_eq!(conflicts, p_conflicts);
+            let (intersection, _) = graph.intersection(&right, policy);
+            let (p_intersection, _) = persistent.intersection(&persistent_right, policy);
+            assert_eq!(intersection, BTreeGraph::from(&p_intersection));
+            let (difference, _) = graph.difference(&right, policy);
+            let (p_difference, _) = persistent.difference(&persistent_right, policy);
+            assert_eq!(difference, BTreeGraph::from(&p_difference));
+            let (symmetric, _) = graph.symmetric_difference(&right, policy);
+            let (p_symmetric, _) = persistent.symmetric_difference(&persistent_right, policy);
+            assert_eq!(symmetric, BTreeGraph::from(&p_symmetric));
+        }
+
+        // Diffs agree, and apply to either implementation.
+        let diff = graph.diff(&right);
+        assert_eq!(diff, persistent.diff(&persistent_right));
+        let snapshot = persistent.snapshot();
+        persistent.apply(&diff)?;
+        assert_eq!(persistent, persistent_right);
+        assert_eq!(
+            persistent.apply(&diff).unwrap_err(),
+            right.clone().apply(&diff).unwrap_err()
+        );
+        assert_eq!(persistent, persistent_right);
+        assert_ne!(snapshot, persistent);
+
+        // Adding an edge adds its vertices, and removing isolated vertices agrees.
+        let mut persistent = PersistentGraph::from(graph.clone());
+        assert_eq!(
+            graph.add_edge_with_vertices(5, 6, 7),
+            persistent.add_edge_with_vertices(5, 6, 7)
+        );
+        assert_eq!(graph.add_vertex(8), persistent.add_vertex(8));
+        persistent.remove_edge(7)?;
+        graph.remove_edge(7)?;
+        let isolated = graph.remove_isolated_vertices();
+        assert_eq!(isolated, persistent.remove_isolated_vertices());
+        assert_eq!(isolated, [3, 5, 6, 8].iter().cloned().collect());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn observed() -> Result<(), Error> {
+        // Record each event as an owned (kind, key) pair.
+        let mut events: Vec<(&str, usize)> = Vec::new();
+        let mut graph: Observed<usize, usize> = Observed::new(BTreeGraph::new());
+        graph.register(|event: &GraphEvent<usize, usize>| {
+            events.push(match event {
+                GraphEvent::VertexAdded(v) => ("vertex added", **v),
+                GraphEvent::VertexRemoved(v) => ("vertex removed", **v),
+                GraphEvent::EdgeAdded(e, _) => ("edge added", **e),
+                GraphEvent::EdgeReplaced(e, _, _) => ("edge replaced", **e),
+                GraphEvent::EdgeRemoved(e, _) => ("edge removed", **e),
+            })
+        });
+
+        // Add three nodes; re-adding one changes nothing, so is not observed.
+        graph.add_vertex(0);
+        graph.add_vertex(1);
+        graph.add_vertex(2);
+        assert!(!graph.add_vertex(2));
+
+        // Add edges into and out of vertex 1, then replace one.
+        graph.add_edge(0, 1, 3)?;
+        graph.add_edge(1, 2, 4)?;
+        graph.add_edge(1, 2, 4)?;
+        graph.add_edge(2, 0, 5)?;
+
+        // Failed mutations are not observed.
+        assert_eq!(
+            graph.add_edge(0, 9, 6).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+        assert_eq!(graph.remove_edge(9).unwrap_err(), Error::EdgeDoesNotExist);
+
+        // Removing an edge, then a vertex which cascades to its incoming and outgoing edges.
+        assert_eq!(graph.remove_edge(5)?, (2, 0));
+        graph.remove_vertex(1)?;
+
+        // The wrapped graph was mutated.
+        let graph = graph.into_inner();
+        assert_eq!(graph.vertices(), [0, 2].iter().collect());
+        assert_eq!(graph.edges().len(), 0);
+
+        assert_eq!(
+            events,
+            vec![
+                ("vertex added", 0),
+                ("vertex added", 1),
+                ("vertex added", 2),
+                ("edge added", 3),
+                ("edge added", 4),
+                ("edge replaced", 4),
+                ("edge added", 5),
+                ("edge removed", 5),
+                ("edge removed", 3),
+                ("edge removed", 4),
+                ("vertex removed", 1),
+            ]
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn remove_vertex_removed_edges() -> Result<(), Error> {
+        // Add three nodes.
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        graph.add_vertex(0);
+        graph.add_vertex(1);
+        graph.add_vertex(2);
+
+        // Add edges into vertex 1, out of it, and a self-loop, along with an unrelated edge.
+        graph.add_edge(0, 1, 3)?;
+        graph.add_edge(1, 2, 4)?;
+        graph.add_edge(1, 1, 5)?;
+        graph.add_edge(2, 0, 6)?;
+
+        let removed = graph.remove_vertex(1)?;
+        let mut exp_incoming: BTreeSet<Edge<usize, usize>> = BTreeSet::new();
+        exp_incoming.insert((3, (0, 1)));
+        let mut exp_outgoing: BTreeSet<Edge<usize, usize>> = BTreeSet::new();
+        exp_outgoing.insert((4, (1, 2)));
+        let mut exp_loops: BTreeSet<Edge<usize, usize>> = BTreeSet::new();
+        exp_loops.insert((5, (1, 1)));
+        assert_eq!(removed.incoming, exp_incoming);
+        assert_eq!(removed.outgoing, exp_outgoing);
+        assert_eq!(removed.loops, exp_loops);
+        assert_eq!(removed.len(), 3);
+        assert_eq!(
+            removed.iter().map(|edge| edge.0).collect::<Vec<usize>>(),
+            vec![3, 4, 5]
+        );
+
+        // Only the unrelated edge remains.
+        assert_eq!(graph.edges(), [6].iter().collect());
+        assert_eq!(graph.get_vertex_value(0).unwrap().len(), 0);
+
+        // Removing an isolated vertex removes no edges.
+        graph.add_vertex(7);
+        assert!(graph.remove_vertex(7)?.is_empty());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn try_remove_vertex() -> Result<(), Error> {
+        let mut graph = triangle()?;
+        let before = graph.clone();
+
+        // Vertices with outgoing, or incoming edges are not removed.
+        assert_eq!(graph.try_remove_vertex(0).unwrap_err(), Error::EdgeExists);
+        assert_eq!(graph.try_remove_vertex(2).unwrap_err(), Error::EdgeExists);
+        assert_eq!(graph, before);
+
+        // Nor are vertices with only a self-loop.
+        graph.add_vertex(3);
+        graph.add_edge(3, 3, 5)?;
+        assert_eq!(graph.try_remove_vertex(3).unwrap_err(), Error::EdgeExists);
+        graph.remove_edge(5)?;
+
+        // Isolated vertices are removed.
+        graph.try_remove_vertex(3)?;
+        assert_eq!(graph, before);
+
+        // Remove vertex which does not exist.
+        assert_eq!(
+            graph.try_remove_vertex(3).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn from_edges() -> Result<(), Error> {
+        // Build the graph from its edges alone, giving edge 4 twice.
+        let graph: BTreeGraph<usize, usize> =
+            BTreeGraph::from_edges(vec![(3, (1, 2)), (4, (1, 0)), (2, (0, 1)), (4, (0, 2))]);
+        assert_eq!(graph, triangle()?);
+
+        // Collecting is equivalent.
+        let collected: BTreeGraph<usize, usize> = vec![(2, (0, 1)), (3, (1, 2)), (4, (0, 2))]
+            .into_iter()
+            .collect();
+        assert_eq!(collected, graph);
+
+        // Self-loops create a single vertex.
+        let graph: BTreeGraph<usize, usize> = BTreeGraph::from_edges(vec![(0, (5, 5))]);
+        assert_eq!(graph.vertices(), [5].iter().collect());
+        assert!(graph.adjacent(5, 5)?);
+
+        // No edges, no vertices.
+        let graph: BTreeGraph<usize, usize> = BTreeGraph::from_edges(Vec::new());
+        assert_eq!(graph, BTreeGraph::new());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn extend() -> Result<(), Error> {
+        let mut graph = triangle()?;
+
+        // Add edges to existing, and new vertices, and move edge 4 to a new source.
+        graph.extend(vec![(5, (2, 0)), (6, (2, 3)), (4, (3, 1))]);
+
+        let mut exp_graph = triangle()?;
+        exp_graph.add_vertex(3);
+        exp_graph.add_edge(2, 0, 5)?;
+        exp_graph.add_edge(2, 3, 6)?;
+        exp_graph.remove_edge(4)?;
+        exp_graph.add_edge(3, 1, 4)?;
+        assert_eq!(graph, exp_graph);
+
+        // Extending an empty graph is the same as building from the edges.
+        let edges: Vec<Edge<usize, usize>> = vec![(2, (0, 1)), (3, (1, 2)), (4, (0, 2))];
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        graph.extend(edges.clone());
+        assert_eq!(graph, BTreeGraph::from_edges(edges));
+
+        // The vertices of a replaced edge value are kept by both.
+        let edges: Vec<Edge<usize, usize>> = vec![(1, (0, 1)), (1, (2, 2))];
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        graph.extend(edges.clone());
+        assert_eq!(graph.vertices(), [0, 1, 2].iter().collect());
+        assert_eq!(graph.connections(0)?, BTreeSet::new());
+        assert!(graph.adjacent(2, 2)?);
+        assert_eq!(graph, BTreeGraph::from_edges(edges.clone()));
+        assert_eq!(graph, edges.into_iter().collect());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn add_edge_with_vertices() -> Result<(), Error> {
+        // Add edges, and with them their vertices.
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        assert!(graph.add_edge_with_vertices(0, 1, 2).is_none());
+        assert!(graph.add_edge_with_vertices(1, 2, 3).is_none());
+        assert!(graph.add_edge_with_vertices(0, 2, 4).is_none());
+        assert_eq!(graph, triangle()?);
+
+        // Moving an edge to a new source takes it out of the old adjacency list.
+        assert_eq!(graph.add_edge_with_vertices(3, 0, 4), Some((0, 2)));
+        assert_eq!(
+            graph.get_vertex_value(0).unwrap(),
+            &[2].iter().cloned().collect()
+        );
+        assert_eq!(
+            graph.get_vertex_value(3).unwrap(),
+            &[4].iter().cloned().collect()
+        );
+        assert!(graph.adjacent(3, 0)?);
+        assert!(!graph.adjacent(0, 2)?);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn entry() -> Result<(), Error> {
+        let mut graph = triangle()?;
+
+        // Occupied entries see the adjacency list of the vertex.
+        match graph.entry(0) {
+            Entry::Occupied(entry) => {
+                assert_eq!(entry.key(), &0);
+                assert_eq!(entry.get(), &[2, 4].iter().cloned().collect());
+            }
+            Entry::Vacant(_) => panic!("vertex 0 exists"),
+        }
+
+        // Vacant entries add the vertex on insert.
+        let mut entry = match graph.entry(3) {
+            Entry::Occupied(_) => panic!("vertex 3 does not exist"),
+            Entry::Vacant(entry) => entry.insert(),
+        };
+        entry.add_edge(1, 5)?;
+        assert_eq!(entry.add_edge(4, 6).unwrap_err(), Error::VertexDoesNotExist);
+        assert!(entry.add_edge_with_vertex(4, 6).is_none());
+        assert_eq!(entry.get().len(), 2);
+        assert!(graph.adjacent(3, 1)?);
+        assert!(graph.adjacent(3, 4)?);
+
+        // Removing through the entry cascades to its edges.
+        let removed = graph.entry(3).or_insert().remove();
+        assert_eq!(removed.outgoing.len(), 2);
+        assert_eq!(graph.vertices().len(), 4);
+        assert_eq!(graph.entry(3).key(), &3);
+
+        // Test passed.
+        Ok(())
+    }
+
+    /// Checks the two maps of the graph agree; every edge is in the adjacency list of its
+    /// source, and only there, and both of its vertices exist.
+    fn assert_consistent(graph: &BTreeGraph<usize, usize>) {
+        for e in graph.edges() {
+            let (x, y) = graph.get_edge_value(*e).unwrap();
+            assert!(graph.get_vertex_value(*x).unwrap().contains(e));
+            assert!(graph.get_vertex_value(*y).is_some());
+        }
+        for v in graph.vertices() {
+            for e in graph.get_vertex_value(*v).unwrap() {
+                assert_eq!(&graph.get_edge_value(*e).unwrap().0, v);
+            }
+        }
+        assert!(graph.validate().is_empty());
+    }
+
+    #[test]
+    fn add_vertex_keeps_edges() -> Result<(), Error> {
+        let mut graph = triangle()?;
+        let before = graph.clone();
+
+        // Re-adding every vertex changes nothing.
+        for v in 0..3 {
+            assert!(!graph.add_vertex(v));
+        }
+        assert_eq!(graph, before);
+        assert_consistent(&graph);
+
+        // Adding a new vertex does.
+        assert!(graph.add_vertex(3));
+        assert_eq!(graph.vertices().len(), 4);
+        assert_consistent(&graph);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn reset_vertex() -> Result<(), Error> {
+        let mut graph = triangle()?;
+        graph.add_edge(0, 0, 5)?;
+
+        // Resetting vertex 0 removes its outgoing edges, and self-loop.
+        let mut exp_removed: BTreeSet<Edge<usize, usize>> = BTreeSet::new();
+        exp_removed.insert((2, (0, 1)));
+        exp_removed.insert((4, (0, 2)));
+        exp_removed.insert((5, (0, 0)));
+        assert_eq!(graph.reset_vertex(0), Some(exp_removed));
+        assert_eq!(graph.get_vertex_value(0).unwrap().len(), 0);
+        assert_eq!(graph.edges(), [3].iter().collect());
+        assert_consistent(&graph);
+
+        // Edges into a vertex are kept when it is reset.
+        graph.add_edge(0, 2, 4)?;
+        assert_eq!(graph.reset_vertex(2), Some(BTreeSet::new()));
+        assert_eq!(graph.edges(), [3, 4].iter().collect());
+        assert_consistent(&graph);
+
+        // Resetting a vertex which does not exist adds it.
+        assert_eq!(graph.reset_vertex(3), None);
+        assert_eq!(graph.vertices().len(), 4);
+        assert_consistent(&graph);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn validate() -> Result<(), Error> {
+        // Graphs built through the API are valid.
+        let graph = triangle()?;
+        assert!(graph.validate().is_empty());
+
+        // Move edge 2 from vertex 0 to vertex 1, and point it at vertex 9 which does not
+        // exist, without updating the adjacency lists.
+        let mut corrupt = graph.clone();
+        corrupt.edges.insert(2, (1, 9));
+        // List edge 7, which does not exist, under vertex 2.
+        corrupt.vertices.get_mut(&2).unwrap().insert(7);
+        // Add edge 8 from vertex 3, which does not exist.
+        corrupt.edges.insert(8, (3, 0));
+
+        let violations = corrupt.validate();
+        assert_eq!(
+            violations,
+            vec![
+                Violation::Unlisted(&2),
+                Violation::MissingTarget(&2, &9),
+                Violation::MissingSource(&8, &3),
+                Violation::WrongSource(&0, &2),
+                Violation::MissingEdge(&2, &7),
+            ]
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    fn graph_tokens(target: usize) -> Vec<serde_test::Token> {
+        use serde_test::Token;
+        // Vertices 0, and 1, with an edge (0, target) = 2.
+        vec![
+            Token::Struct {
+                name: "BTreeGraph",
+                len: 2,
+            },
+            Token::Str("vertices"),
+            Token::Map { len: Some(2) },
+            Token::U64(0),
+            Token::Seq { len: Some(1) },
+            Token::U64(2),
+            Token::SeqEnd,
+            Token::U64(1),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::MapEnd,
+            Token::Str("edges"),
+            Token::Map { len: Some(1) },
+            Token::U64(2),
+            Token::Tuple { len: 2 },
+            Token::U64(0),
+            Token::U64(target as u64),
+            Token::TupleEnd,
+            Token::MapEnd,
+            Token::StructEnd,
+        ]
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn deserialize() -> Result<(), Error> {
+        use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_tokens, Token};
+
+        // Valid graphs round trip.
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        graph.add_vertex(0);
+        graph.add_vertex(1);
+        graph.add_edge(0, 1, 2)?;
+        assert_tokens(&graph, &graph_tokens(1));
+
+        // An edge to a vertex which does not exist is rejected.
+        assert_de_tokens_error::<BTreeGraph<usize, usize>>(
+            &graph_tokens(3),
+            "invalid graph: the target vertex 3 of the edge 2 does not exist (1 violation(s) in total)",
+        );
+
+        // Unless the input is trusted.
+        #[derive(serde::Deserialize, PartialEq, Debug)]
+        struct Trusted(
+            #[serde(deserialize_with = "BTreeGraph::deserialize_unchecked")]
+            BTreeGraph<usize, usize>,
+        );
+        let mut unchecked = graph.clone();
+        unchecked.edges.insert(2, (0, 3));
+        assert_eq!(unchecked.validate().len(), 1);
+        let mut tokens = vec![Token::NewtypeStruct { name: "Trusted" }];
+        tokens.extend(graph_tokens(3));
+        assert_de_tokens(&Trusted(unchecked), &tokens);
+
+        // An edge missing from the adjacency list of its source is rejected.
+        let mut tokens = graph_tokens(1);
+        tokens.splice(4..7, vec![Token::Seq { len: Some(0) }, Token::SeqEnd]);
+        assert_de_tokens_error::<BTreeGraph<usize, usize>>(
+            &tokens,
+            "invalid graph: the edge 2 is not listed by its source vertex (1 violation(s) in total)",
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+    struct Readable(#[serde(with = "crate::readable")] BTreeGraph<usize, usize>);
+
+    #[cfg(feature = "serde")]
+    fn readable_tokens(from: usize, to: usize) -> Vec<serde_test::Token> {
+        use serde_test::Token;
+        // Vertices 0, and 1, with an edge (from, to) = 2.
+        vec![
+            Token::NewtypeStruct { name: "Readable" },
+            Token::Struct {
+                name: "BTreeGraph",
+                len: 2,
+            },
+            Token::Str("vertices"),
+            Token::Seq { len: Some(2) },
+            Token::U64(0),
+            Token::U64(1),
+            Token::SeqEnd,
+            Token::Str("edges"),
+            Token::Seq { len: Some(1) },
+            Token::Struct {
+                name: "Edge",
+                len: 3,
+            },
+            Token::Str("id"),
+            Token::U64(2),
+            Token::Str("from"),
+            Token::U64(from as u64),
+            Token::Str("to"),
+            Token::U64(to as u64),
+            Token::StructEnd,
+            Token::SeqEnd,
+            Token::StructEnd,
+        ]
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn readable() -> Result<(), Error> {
+        use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_tokens, Token};
+
+        // Graphs round trip.
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        graph.add_vertex(0);
+        graph.add_vertex(1);
+        graph.add_edge(0, 1, 2)?;
+        assert_tokens(&Readable(graph), &readable_tokens(0, 1));
+
+        // Edges from, or to vertices which are not listed are rejected.
+        assert_de_tokens_error::<Readable>(
+            &readable_tokens(3, 1),
+            "invalid graph: an edge goes from a vertex which is not listed",
+        );
+        assert_de_tokens_error::<Readable>(
+            &readable_tokens(0, 3),
+            "invalid graph: an edge goes to a vertex which is not listed",
+        );
+
+        // Vertices listed more than once are rejected.
+        let mut tokens = readable_tokens(0, 1);
+        tokens[5] = Token::U64(0);
+        assert_de_tokens_error::<Readable>(
+            &tokens,
+            "invalid graph: a vertex is listed more than once",
+        );
+
+        // Either list may be left out.
+        assert_de_tokens(
+            &Readable(BTreeGraph::new()),
+            &[
+                Token::NewtypeStruct { name: "Readable" },
+                Token::Struct {
+                    name: "BTreeGraph",
+                    len: 0,
+                },
+                Token::StructEnd,
+            ],
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde_json")]
+    fn json() -> Result<(), Error> {
+        let graph = triangle()?;
+        let json = graph.to_json().unwrap();
+        assert_eq!(
+            json,
+            r#"{"vertices":{"0":[2,4],"1":[3],"2":[]},"edges":{"2":[0,1],"3":[1,2],"4":[0,2]}}"#
+        );
+        assert_eq!(BTreeGraph::from_json(&json).unwrap(), graph);
+
+        // Encoding only needs the vertices, and edges to be serializable.
+        let mut borrowed: BTreeGraph<&str, usize> = BTreeGraph::new();
+        borrowed.add_vertex("origin");
+        borrowed.add_vertex("destination");
+        borrowed.add_edge("origin", "destination", 10)?;
+        assert_eq!(
+            borrowed.to_json().unwrap(),
+            r#"{"vertices":{"destination":[],"origin":[10]},"edges":{"10":["origin","destination"]}}"#
+        );
+
+        // Graphs which break their invariants are rejected.
+        let json = r#"{"vertices":{"0":[2]},"edges":{"2":[0,1]}}"#;
+        let err = BTreeGraph::<usize, usize>::from_json(json).unwrap_err();
+        assert_eq!(
+            alloc::format!("{}", err),
+            "invalid graph: the target vertex 1 of the edge 2 does not exist \
+             (1 violation(s) in total)"
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde_yaml")]
+    fn yaml() -> Result<(), Error> {
+        let graph = triangle()?;
+        let yaml = graph.to_yaml().unwrap();
+        assert_eq!(BTreeGraph::from_yaml(&yaml).unwrap(), graph);
+
+        // The readable representation is hand-editable.
+        #[derive(serde::Deserialize)]
+        struct Config {
+            #[serde(with = "crate::readable")]
+            graph: BTreeGraph<usize, usize>,
+        }
+        let yaml = "
+graph:
+  vertices: [0, 1, 2]
+  edges:
+    - { id: 2, from: 0, to: 1 }
+    - { id: 3, from: 1, to: 2 }
+    - { id: 4, from: 0, to: 2 }
+";
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.graph, graph);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "serde_cbor")]
+    fn cbor() -> Result<(), Error> {
+        let graph = triangle()?;
+        let cbor = graph.to_cbor().unwrap();
+        assert_eq!(BTreeGraph::from_cbor(&cbor).unwrap(), graph);
+
+        // Truncated input is rejected.
+        assert!(BTreeGraph::<usize, usize>::from_cbor(&cbor[..cbor.len() - 1]).is_err());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn dot() -> Result<(), Error> {
+        let graph = triangle()?;
+        let directed = alloc::format!("{}", crate::dot::Dot::new(&graph));
+        assert_eq!(
+            directed,
+            r#"digraph {
+    "0";
+    "1";
+    "2";
+    "0" -> "1" [id="2", label="2"];
+    "1" -> "2" [id="3", label="3"];
+    "0" -> "2" [id="4", label="4"];
+}
+"#
+        );
+
+        // Labels, and attributes are customizable, and quotes are escaped.
+        let undirected = crate::dot::Dot::new(&graph)
+            .undirected()
+            .vertex_label(|v| alloc::format!("\"v{}\"", v))
+            .vertex_attributes(|_| vec![(String::from("shape"), String::from("box"))])
+            .edge_label(|e| alloc::format!("e{}", e))
+            .edge_attributes(|_, (x, _)| vec![(String::from("weight"), alloc::format!("{}", x))]);
+        assert_eq!(
+            alloc::format!("{}", undirected),
+            r#"graph {
+    "0" [label="\"v0\"", shape="box"];
+    "1" [label="\"v1\"", shape="box"];
+    "2" [label="\"v2\"", shape="box"];
+    "0" -- "1" [id="2", label="e2", weight="0"];
+    "1" -- "2" [id="3", label="e3", weight="1"];
+    "0" -- "2" [id="4", label="e4", weight="0"];
+}
+"#
+        );
+
+        // Both outputs parse back to the same graph.
+        let mut expected: BTreeGraph<String, String> = BTreeGraph::new();
+        for v in ["0", "1", "2"].iter() {
+            expected.add_vertex(String::from(*v));
+        }
+        for (x, y, e) in [("0", "1", "2"), ("1", "2", "3"), ("0", "2", "4")].iter() {
+            expected.add_edge(String::from(*x), String::from(*y), String::from(*e))?;
+        }
+        assert_eq!(crate::dot::parse(&directed), Ok(expected.clone()));
+        assert_eq!(
+            crate::dot::parse(&alloc::format!("{}", undirected)),
+            Ok(expected)
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn dot_parse() -> Result<(), Error> {
+        let graph = crate::dot::parse(
+            "strict digraph G {
+    // Attribute statements are ignored.
+    node [shape=box]; rankdir=LR
+    a; \"b \\\"c\\\"\" /* a quoted id */
+    a -> d [id=e1, color=red]
+    # d is created by the edge.
+    d -> a [id=\"e2\"];
+}",
+        )
+        .unwrap();
+        assert_eq!(graph.vertices().len(), 3);
+        assert!(graph.vertices().contains(&String::from("b \"c\"")));
+        assert_eq!(
+            graph.get_edge_value(String::from("e1")),
+            Some(&(String::from("a"), String::from("d")))
+        );
+        assert!(graph.validate().is_empty());
+
+        // Errors report the line they occur on.
+        let error = |line, message| crate::ParseError { line, message };
+        assert_eq!(
+            crate::dot::parse("digraph {\n  a -> b;\n}"),
+            Err(error(2, "edge statement has no id"))
+        );
+        assert_eq!(
+            crate::dot::parse("graph {\n\n  a -> b [id=1];\n}"),
+            Err(error(3, "edge operator does not match the graph"))
+        );
+        assert_eq!(
+            crate::dot::parse("digraph {\n  a -> b [id=1];\n  b -> c [id=1];\n}"),
+            Err(error(3, "edge id is used more than once"))
+        );
+        assert_eq!(
+            crate::dot::parse("digraph {\n  a -> b -> c [id=1];\n}"),
+            Err(error(2, "chained edge statements are not supported"))
+        );
+        assert_eq!(
+            crate::dot::parse("digraph {\n  subgraph { a }\n}"),
+            Err(error(2, "subgraphs are not supported"))
+        );
+        assert_eq!(
+            crate::dot::parse("digraph {\n  \"a\n"),
+            Err(error(2, "unterminated string"))
+        );
+        assert_eq!(
+            crate::dot::parse("digraph {\n  a;\n"),
+            Err(error(2, "expected '}'"))
+        );
+        assert_eq!(
+            crate::dot::parse("tree {}"),
+            Err(error(1, "expected 'graph', or 'digraph'"))
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn graphml() -> Result<(), Error> {
+        let graph = triangle()?;
+        let graphml = graph.to_graphml();
+        assert_eq!(
+            graphml,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <graph edgedefault="directed">
+    <node id="0"/>
+    <node id="1"/>
+    <node id="2"/>
+    <edge id="2" source="0" target="1"/>
+    <edge id="3" source="1" target="2"/>
+    <edge id="4" source="0" target="2"/>
+  </graph>
+</graphml>
+"#
+        );
+        assert_eq!(BTreeGraph::from_graphml(&graphml), Ok(graph.clone()));
+
+        // An empty graph round trips.
+        let empty: BTreeGraph<usize, usize> = BTreeGraph::new();
+        assert_eq!(BTreeGraph::from_graphml(&empty.to_graphml()), Ok(empty));
+
+        // Data attributes round trip, along with ids which need escaping.
+        let mut graph: BTreeGraph<String, String> = BTreeGraph::new();
+        graph.add_vertex(String::from("<a & b>"));
+        graph.add_vertex(String::from("\"c\""));
+        graph.add_edge(
+            String::from("<a & b>"),
+            String::from("\"c\""),
+            String::from("'e'"),
+        )?;
+        let graphml = graph.to_graphml_with(
+            |v| {
+                vec![
+                    (String::from("length"), alloc::format!("{}", v.len())),
+                    (String::from("name"), v.clone()),
+                ]
+            },
+            |_, (x, _)| vec![(String::from("name"), x.clone())],
+        );
+        assert!(
+            graphml.contains(r#"<key id="d2" for="edge" attr.name="name" attr.type="string"/>"#)
+        );
+        let mut vertex_data: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+        let mut edge_data: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+        let parsed = BTreeGraph::from_graphml_with(
+            &graphml,
+            |v: &String, data| {
+                vertex_data.insert(v.clone(), data.to_vec());
+            },
+            |e: &String, data| {
+                edge_data.insert(e.clone(), data.to_vec());
+            },
+        );
+        assert_eq!(parsed, Ok(graph));
+        assert_eq!(
+            vertex_data.get("<a & b>"),
+            Some(&vec![
+                (String::from("length"), String::from("7")),
+                (String::from("name"), String::from("<a & b>")),
+            ])
+        );
+        assert_eq!(
+            edge_data.get("'e'"),
+            Some(&vec![(String::from("name"), String::from("<a & b>"))])
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn graphml_parse() -> Result<(), Error> {
+        let mut colors: BTreeMap<usize, String> = BTreeMap::new();
+        let graph = BTreeGraph::<usize, usize>::from_graphml_with(
+            r##"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<!-- Written by hand, in the style of yEd. -->
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns" xmlns:y="http://www.yworks.com/xml/graphml">
+  <key id="color" for="node" attr.name="color" attr.type="string">
+    <default>white</default>
+  </key>
+  <key id="graphics" for="node" yfiles.type="nodegraphics"/>
+  <graph id="G" edgedefault='undirected'>
+    <edge id="2" source="0" target="1"/>
+    <node id="0"><data key="color"><![CDATA[r&d]]></data></node>
+    <node id="1">
+      <data key="graphics"><y:ShapeNode><y:Fill color="#FF0000"/></y:ShapeNode></data>
+    </node>
+  </graph>
+</graphml>"##,
+            |v, data| {
+                for (name, value) in data.iter() {
+                    if name == "color" {
+                        colors.insert(*v, value.clone());
+                    }
+                }
+            },
+            |_, _| (),
+        )
+        .unwrap();
+        assert_eq!(graph.vertices().len(), 2);
+        assert_eq!(graph.get_edge_value(2), Some(&(0, 1)));
+        assert_eq!(colors.get(&0), Some(&String::from("r&d")));
+        assert_eq!(colors.get(&1), Some(&String::from("white")));
+
+        // Errors report the line they occur on.
+        let error = |line, message| crate::ParseError { line, message };
+        let parse = BTreeGraph::<usize, usize>::from_graphml;
+        assert_eq!(
+            parse("<graphml>\n<graph>\n<node id=\"0\"/>\n<edge source=\"0\" target=\"0\"/>\n</graph>\n</graphml>"),
+            Err(error(4, "missing id, source, or target attribute"))
+        );
+        assert_eq!(
+            parse("<graphml>\n<graph>\n<node id=\"a\"/>\n</graph>\n</graphml>"),
+            Err(error(3, "node id is not a valid vertex"))
+        );
+        assert_eq!(
+            parse("<graphml>\n<graph>\n<node id=\"0\"/>\n<node id=\"0\"/>\n</graph>\n</graphml>"),
+            Err(error(4, "node id is used more than once"))
+        );
+        assert_eq!(
+            parse("<graphml>\n<graph>\n<edge id=\"1\" source=\"0\" target=\"0\"/>\n</graph>\n</graphml>"),
+            Err(error(3, "edge target is not a declared node"))
+        );
+        assert_eq!(
+            parse("<graphml>\n<graph>\n<node id=\"0\"><graph/></node>\n</graph>\n</graphml>"),
+            Err(error(3, "nested graphs are not supported"))
+        );
+        assert_eq!(
+            parse("<graphml>\n<graph>\n</graphml>"),
+            Err(error(3, "end tag does not match the open element"))
+        );
+        assert_eq!(
+            parse("<graphml>\n<graph id=\"&nbsp;\">"),
+            Err(error(2, "unknown entity"))
+        );
+        assert_eq!(
+            parse("<graph/>"),
+            Err(error(1, "expected a graphml element"))
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn edge_list() -> Result<(), Error> {
+        let mut graph = triangle()?;
+        graph.add_vertex(3);
+        assert_eq!(graph.to_edge_list(' '), "2 0 1\n3 1 2\n4 0 2\n3\n");
+
+        // Both whitespace, and comma separated lists round trip.
+        for separator in [' ', '\t', ','].iter() {
+            let text = graph.to_edge_list(*separator);
+            assert_eq!(BTreeGraph::from_edge_list(&text), Ok(graph.clone()));
+        }
+
+        // Blank lines, and comments are skipped.
+        let parsed =
+            BTreeGraph::from_edge_list("# id source target\n\n2, 0, 1\n3 1   2\n4,0,2\n3\n");
+        assert_eq!(parsed, Ok(graph));
+
+        // Errors report the line they occur on.
+        let error = |line, message| crate::ParseError { line, message };
+        let parse = BTreeGraph::<usize, usize>::from_edge_list;
+        assert_eq!(
+            parse("2 0 1\n3 1\n"),
+            Err(error(
+                2,
+                "expected an edge, source, and target, or a single vertex"
+            ))
+        );
+        assert_eq!(
+            parse("# comment\n2 0 one\n"),
+            Err(error(2, "target vertex is not valid"))
+        );
+        assert_eq!(
+            parse("2 0 1\n\n2 1 0\n"),
+            Err(error(3, "edge is listed more than once"))
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn adjacency_list() -> Result<(), Error> {
+        let mut graph = triangle()?;
+        graph.add_vertex(3);
+        assert_eq!(graph.to_adjacency_list(), "0: 1 2\n1: 2\n2:\n3:\n");
+
+        // Edges are named by the closure; here, the same as the left set operation graph.
+        let text = graph.to_adjacency_list();
+        let parsed = BTreeGraph::from_adjacency_list(&text, |x: &usize, y: &usize| 2 * y - x);
+        assert_eq!(parsed, Ok(graph));
+
+        // Targets are created, and may be separated by commas.
+        let parsed =
+            BTreeGraph::<usize, usize>::from_adjacency_list("0: 1, 2", |x, y| 2 * y - x).unwrap();
+        assert_eq!(parsed.vertices().len(), 3);
+        assert_eq!(parsed.get_edge_value(4), Some(&(0, 2)));
+
+        // Errors report the line they occur on.
+        let error = |line, message| crate::ParseError { line, message };
+        let parse = |text| BTreeGraph::<usize, usize>::from_adjacency_list(text, |_, y| *y);
+        assert_eq!(
+            parse("0: 1\n1 0\n"),
+            Err(error(2, "expected a vertex followed by ':'"))
+        );
+        assert_eq!(
+            parse("0: 1\n# comment\n0: 2\n"),
+            Err(error(3, "vertex is listed more than once"))
+        );
+        assert_eq!(
+            parse("0: 1\n1: 1\n"),
+            Err(error(2, "edge is named more than once"))
+        );
+        assert_eq!(
+            parse("0: 1 x\n"),
+            Err(error(1, "target vertex is not valid"))
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "fmt")]
+    fn display() -> Result<(), Error> {
+        let mut graph = triangle()?;
+        graph.add_vertex(10);
+        assert_eq!(
+            alloc::format!("{}", graph),
+            "\
+vertex | edges
+-------+---------------
+0      | 2 -> 1, 4 -> 2
+1      | 3 -> 2
+2      |
+10     |
+"
+        );
+
+        // Views are displayed as seen through them.
+        assert_eq!(
+            alloc::format!("{}", Reversed::new(&graph)),
+            "\
+vertex | edges
+-------+---------------
+0      |
+1      | 2 -> 0
+2      | 3 -> 1, 4 -> 0
+10     |
+"
+        );
+        let filtered = Filtered::new(&graph, |v| *v != 10, |e, _| *e != 4);
+        assert_eq!(
+            alloc::format!("{}", filtered),
+            "\
+vertex | edges
+-------+-------
+0      | 2 -> 1
+1      | 3 -> 2
+2      |
+"
+        );
+        let vertices: BTreeSet<usize> = [0, 2].iter().cloned().collect();
+        assert_eq!(
+            alloc::format!("{}", SubgraphView::new(&graph, &vertices)),
+            "\
+vertex | edges
+-------+-------
+0      | 4 -> 2
+2      |
+"
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "fmt")]
+    fn ascii_tree() -> Result<(), Error> {
+        // A forest.
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        for v in 0..6 {
+            graph.add_vertex(v);
+        }
+        graph.add_edge(0, 1, 10)?;
+        graph.add_edge(0, 2, 11)?;
+        graph.add_edge(1, 3, 12)?;
+        graph.add_edge(4, 5, 13)?;
+        assert_eq!(
+            alloc::format!("{}", graph.ascii_tree()),
+            "\
+0
+|-- [10] 1
+|   `-- [12] 3
+`-- [11] 2
+4
+`-- [13] 5
+"
+        );
+
+        // Vertices only on a cycle start a tree of their own, and are not repeated.
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        for v in 0..3 {
+            graph.add_vertex(v);
+        }
+        graph.add_edge(1, 2, 10)?;
+        graph.add_edge(2, 1, 11)?;
+        graph.add_edge(2, 2, 12)?;
+        assert_eq!(
+            alloc::format!("{}", graph.ascii_tree()),
+            "\
+0
+1
+`-- [10] 2
+    |-- [11] 1 (*)
+    `-- [12] 2 (*)
+"
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn binary() -> Result<(), Error> {
+        let graph = triangle()?;
+        assert_eq!(BTreeGraph::from_bytes(&graph.to_bytes()), Ok(graph));
+        let empty: BTreeGraph<String, char> = BTreeGraph::new();
+        assert_eq!(BTreeGraph::from_bytes(&empty.to_bytes()), Ok(empty));
+
+        let mut graph: BTreeGraph<u8, u8> = BTreeGraph::new();
+        graph.add_vertex(1);
+        graph.add_vertex(2);
+        graph.add_edge(1, 2, 7)?;
+        let bytes = graph.to_bytes();
+        let header = [b'B', b'T', b'G', b'R', 1];
+        let vertices = [5, 2, 1, 1, 1, 2];
+        let edges = [5, 1, 1, 7, 0, 1];
+        let checksum = [0xd1, 0x7b, 0xee, 0xd2];
+        assert_eq!(bytes, [&header[..], &vertices, &edges, &checksum].concat());
+        assert_eq!(BTreeGraph::from_bytes(&bytes), Ok(graph));
+
+        // Damaged, and invalid encodings are rejected.
+        let decode = BTreeGraph::<u8, u8>::from_bytes;
+        let mut damaged = bytes.clone();
+        damaged[8] = 3;
+        assert_eq!(decode(&damaged), Err(DecodeError::Checksum));
+        assert_eq!(
+            decode(&bytes[..bytes.len() - 1]),
+            Err(DecodeError::Checksum)
+        );
+        assert_eq!(decode(&bytes[..6]), Err(DecodeError::Truncated));
+        assert_eq!(decode(b"GRAPH"), Err(DecodeError::Magic));
+        let mut version = bytes.clone();
+        version[4] = 2;
+        assert_eq!(decode(&version), Err(DecodeError::Version(2)));
+        // Vertices out of order.
+        let unordered = [
+            &header[..],
+            &[5, 2, 1, 2, 1, 1],
+            &[1, 0],
+            &[0xd0, 0xee, 0xb7, 0x76],
+        ]
+        .concat();
+        assert_eq!(decode(&unordered), Err(DecodeError::Vertex));
+        // An edge to a third vertex.
+        let index = [
+            &header[..],
+            &vertices,
+            &[5, 1, 1, 7, 0, 2],
+            &[0x6b, 0x2a, 0xe7, 0x4b],
+        ]
+        .concat();
+        assert_eq!(decode(&index), Err(DecodeError::VertexIndex));
+        // A byte left over in the edge section.
+        let section = [
+            &header[..],
+            &vertices,
+            &[6, 1, 1, 7, 0, 1, 0],
+            &[0x29, 0xf9, 0xec, 0x12],
+        ]
+        .concat();
+        assert_eq!(decode(&section), Err(DecodeError::SectionLength));
+        // A vertex which does not decode as the vertex type.
+        assert_eq!(
+            BTreeGraph::<u16, u8>::from_bytes(&bytes),
+            Err(DecodeError::Vertex)
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn matrix() -> Result<(), Error> {
+        let graph = triangle()?;
+        let (vertices, matrix) = graph.to_adjacency_matrix();
+        assert_eq!(vertices, vec![&0, &1, &2]);
+        assert_eq!(matrix, vec![0, 1, 1, 0, 0, 1, 0, 0, 0]);
+        let labels: Vec<usize> = vertices.into_iter().cloned().collect();
+        let from = BTreeGraph::from_adjacency_matrix(labels, &matrix, |x, y| 2 * y - x);
+        assert_eq!(from, Ok(graph.clone()));
+
+        let csr = graph.to_csr();
+        assert_eq!(csr.vertices, vec![&0, &1, &2]);
+        assert_eq!(csr.offsets, vec![0, 2, 3, 3]);
+        assert_eq!(csr.targets, vec![1, 2, 2]);
+        assert_eq!(csr.edges, vec![&2, &4, &3]);
+
+        let (vertices, edges, matrix) = graph.to_incidence_matrix();
+        assert_eq!(vertices, vec![&0, &1, &2]);
+        assert_eq!(edges, vec![&2, &3, &4]);
+        assert_eq!(matrix, vec![-1, 0, -1, 1, -1, 0, 0, 1, 1]);
+
+        // Parallel edges are counted, and loops are on the diagonal, but cancel out in the
+        // incidence matrix.
+        let mut graph = graph;
+        graph.add_edge(0, 1, 5)?;
+        graph.add_edge(2, 2, 6)?;
+        let (_, matrix) = graph.to_adjacency_matrix();
+        assert_eq!(matrix, vec![0, 2, 1, 0, 0, 1, 0, 0, 1]);
+        let (_, _, matrix) = graph.to_incidence_matrix();
+        assert_eq!(&matrix[10..], &[0, 1, 1, 0, 0]);
+        let mut next = 1;
+        let from = BTreeGraph::<usize, usize>::from_adjacency_matrix(
+            vec![0, 1, 2],
+            &graph.to_adjacency_matrix().1,
+            |_, _| {
+                next += 1;
+                next
+            },
+        )?;
+        assert_eq!(from.edges().len(), 5);
+        assert_consistent(&from);
+
+        // Mismatched, or invalid input raises an error.
+        let edge = |x: &usize, y: &usize| x + y;
+        assert_eq!(
+            BTreeGraph::from_adjacency_matrix(vec![0, 1], &[0, 1, 0], edge),
+            Err(Error::ImproperDimension)
+        );
+        assert_eq!(
+            BTreeGraph::from_adjacency_matrix(vec![0, 0], &[0, 0, 0, 0], edge),
+            Err(Error::VertexExists)
+        );
+        assert_eq!(
+            BTreeGraph::from_adjacency_matrix(vec![0, 1], &[0, 1, 1, 0], edge),
+            Err(Error::EdgeExists)
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn freeze() -> Result<(), Error> {
+        let mut graph = triangle()?;
+        graph.add_vertex(3);
+        graph.add_edge(2, 2, 5)?;
+        let frozen = graph.freeze();
+
+        // The read traits agree with the graph it was frozen from.
+        assert_eq!(frozen.vertices(), graph.vertices());
+        assert_eq!(frozen.edges(), graph.edges());
+        for e in 0..7 {
+            assert_eq!(frozen.get_edge_value(e), graph.get_edge_value(e));
+        }
+        for x in 0..5 {
+            assert_eq!(frozen.connections(x), graph.connections(x));
+            for y in 0..5 {
+                assert_eq!(frozen.adjacent(x, y), graph.adjacent(x, y));
+            }
+        }
+
+        // So generic algorithms work on either.
+        fn reachable<G>(graph: &G, v: usize) -> BTreeSet<usize>
+        where
+            G: Connections<usize, Error = Error>,
+        {
+            let mut seen: BTreeSet<usize> = BTreeSet::new();
+            let mut stack = vec![v];
+            while let Some(x) = stack.pop() {
+                if seen.insert(x) {
+                    stack.extend(graph.connections(x).unwrap().into_iter().cloned());
+                }
+            }
+            seen
+        }
+        assert_eq!(reachable(&frozen, 1), reachable(&graph, 1));
+
+        // Vertices are numbered densely, in ascending order.
+        assert_eq!(frozen.vertex_count(), 4);
+        assert_eq!(frozen.edge_count(), 4);
+        assert_eq!(frozen.index_of(&2), Some(2));
+        assert_eq!(frozen.index_of(&4), None);
+        assert_eq!(frozen.vertex(3), Some(&3));
+        assert_eq!(frozen.vertex(4), None);
+        assert_eq!(frozen.targets(0), &[1, 2]);
+        assert_eq!(frozen.targets(2), &[2]);
+        assert_eq!(frozen.targets(3), &[] as &[usize]);
+        assert_eq!(frozen.targets(4), &[] as &[usize]);
+        let edges: Vec<(&usize, usize)> = frozen.edges_from(0).collect();
+        assert_eq!(edges, vec![(&2, 1), (&4, 2)]);
+        assert_eq!(frozen.edges_from(4).count(), 0);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn add_edge_moves_source() -> Result<(), Error> {
+        let mut graph: BTreeGraph<usize, usize> = BTreeGraph::new();
+        for v in 0..3 {
+            graph.add_vertex(v);
+        }
+        graph.add_edge(0, 1, 7)?;
+
+        // Re-adding an edge with a new source moves it out of the old adjacency list.
+        assert_eq!(graph.add_edge(2, 1, 7)?, Some((0, 1)));
+        assert!(graph.validate().is_empty());
+        assert_eq!(graph.connections(0)?, BTreeSet::new());
+        assert!(graph.adjacent(2, 1)?);
+        assert!(!graph.adjacent(0, 1)?);
+
+        // Rolling the move back restores the old adjacency list.
+        let before = graph.clone();
+        let result = graph.transaction(|tx| -> Result<(), Error> {
+            tx.add_edge(0, 1, 7)?;
+            tx.remove_edge(8)?;
+            Ok(())
+        });
+        assert_eq!(result.unwrap_err(), Error::EdgeDoesNotExist);
+        assert_eq!(graph, before);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn views_in_generic_algorithms() -> Result<(), Error> {
+        // Lists every edge, checking each read trait agrees on it.
+        fn edge_list<G>(graph: &G) -> Vec<(usize, usize, usize)>
+        where
+            G: Vertices<usize>
+                + Edges<usize>
+                + GetEdgeValue<usize, usize>
+                + Adjacent<usize, Error = Error>
+                + Connections<usize, Error = Error>,
+        {
+            let mut edges: Vec<(usize, usize, usize)> = Vec::new();
+            for e in graph.edges() {
+                let (x, y) = graph.get_edge_value(*e).unwrap();
+                assert!(graph.vertices().contains(x));
+                assert!(graph.adjacent(*x, *y).unwrap());
+                assert!(graph.connections(*x).unwrap().contains(y));
+                edges.push((*e, *x, *y));
+            }
+            edges
+        }
+
+        // Every view agrees with the graph it stands for.
+        let graph = triangle()?;
+        let mut reversed: BTreeGraph<usize, usize> = BTreeGraph::new();
+        reversed.extend(vec![(2, (1, 0)), (3, (2, 1)), (4, (2, 0))]);
+        assert_eq!(edge_list(&Reversed::new(&graph)), edge_list(&reversed));
+
+        let filtered = Filtered::new(&graph, |v| *v != 1, |e, _| *e != 3);
+        assert_eq!(
+            edge_list(&filtered),
+            edge_list(&graph.filter_map(|v| *v != 1, |e, _| *e != 3))
+        );
+
+        let vertices: BTreeSet<usize> = [1, 2].iter().cloned().collect();
+        assert_eq!(
+            edge_list(&SubgraphView::new(&graph, &vertices)),
+            edge_list(&graph.induced_subgraph(&vertices))
+        );
+
+        // Test passed.
+        Ok(())
+    }
 }
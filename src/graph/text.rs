@@ -0,0 +1,197 @@
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Write};
+use core::str::FromStr;
+
+use crate::{AddEdgeWithVertices, AddVertex, BTreeGraph, ParseError};
+
+/// Plain text formats, one line per edge, or vertex, using the `Display` output of each
+/// vertex, and edge. Vertices, or edges whose output contains whitespace, or commas can not
+/// be read back.
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord + Display,
+    E: Ord + Display,
+{
+    /// Writes the graph as an edge list, with a line `edge source target` for each edge,
+    /// its fields separated by the separator, such as `' '`, `'\t'`, or `','`. Vertices
+    /// without edges follow, each on a line of its own.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, AddEdge};
+    /// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+    /// graph.add_vertex(String::from("origin"));
+    /// graph.add_vertex(String::from("destination"));
+    /// graph.add_vertex(String::from("elsewhere"));
+    /// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+    ///
+    /// assert_eq!(graph.to_edge_list(','), "10,origin,destination\nelsewhere\n");
+    /// ```
+    pub fn to_edge_list(&self, separator: char) -> String {
+        let mut out = String::new();
+        for (e, (x, y)) in self.edges.iter() {
+            let _ = writeln!(out, "{}{}{}{}{}", e, separator, x, separator, y);
+        }
+        let targets: BTreeSet<&V> = self.edges.values().map(|(_, y)| y).collect();
+        for (v, edges) in self.vertices.iter() {
+            if edges.is_empty() && !targets.contains(v) {
+                let _ = writeln!(out, "{}", v);
+            }
+        }
+        out
+    }
+
+    /// Writes the graph as an adjacency list, with a line `vertex: target target` for each
+    /// vertex, listing the target of each of its edges. Edges are not named; see
+    /// `from_adjacency_list`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, AddEdge};
+    /// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+    /// graph.add_vertex(String::from("origin"));
+    /// graph.add_vertex(String::from("destination"));
+    /// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+    ///
+    /// assert_eq!(graph.to_adjacency_list(), "destination:\norigin: destination\n");
+    /// ```
+    pub fn to_adjacency_list(&self) -> String {
+        let mut out = String::new();
+        for (v, edges) in self.vertices.iter() {
+            let _ = write!(out, "{}:", v);
+            for (_, y) in edges.iter().filter_map(|e| self.edges.get(e)) {
+                let _ = write!(out, " {}", y);
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Reading the plain text formats. Blank lines, and lines starting with `#` are skipped.
+/// Fields are separated by commas, or whitespace.
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord + Clone + FromStr,
+    E: Ord + Clone + FromStr,
+{
+    /// Reads an edge list, with a line `edge source target` for each edge, and a line
+    /// `vertex` for each vertex without edges. Vertices are created for the edges which
+    /// go from, or to them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, GetEdgeValue, Vertices};
+    /// let graph: BTreeGraph<String, usize> = BTreeGraph::from_edge_list("
+    ///     10, origin, destination
+    ///     elsewhere
+    /// ").unwrap();
+    ///
+    /// assert_eq!(graph.vertices().len(), 3);
+    /// assert_eq!(
+    ///     graph.get_edge_value(10),
+    ///     Some(&(String::from("origin"), String::from("destination")))
+    /// );
+    /// ```
+    pub fn from_edge_list(input: &str) -> Result<Self, ParseError> {
+        let mut graph: BTreeGraph<V, E> = BTreeGraph::new();
+        for (line, text) in lines(input) {
+            match fields(text).as_slice() {
+                [v] => {
+                    graph.add_vertex(parse(v, line, "vertex is not valid")?);
+                }
+                [e, x, y] => {
+                    let e: E = parse(e, line, "edge is not valid")?;
+                    let x: V = parse(x, line, "source vertex is not valid")?;
+                    let y: V = parse(y, line, "target vertex is not valid")?;
+                    if graph.edges.contains_key(&e) {
+                        return Err(error(line, "edge is listed more than once"));
+                    }
+                    graph.add_edge_with_vertices(x, y, e);
+                }
+                _ => {
+                    return Err(error(
+                        line,
+                        "expected an edge, source, and target, or a single vertex",
+                    ))
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Reads an adjacency list, with a line `vertex: target target` for each vertex, naming
+    /// each edge with the closure, which is given its source, and target. Vertices are
+    /// created for the edges which go to them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, Edges};
+    /// let mut count = 0;
+    /// let graph: BTreeGraph<String, usize> = BTreeGraph::from_adjacency_list(
+    ///     "origin: destination elsewhere",
+    ///     |_, _| {
+    ///         count += 1;
+    ///         count
+    ///     },
+    /// ).unwrap();
+    ///
+    /// assert_eq!(graph.edges().len(), 2);
+    /// ```
+    pub fn from_adjacency_list<F>(input: &str, mut edge: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(&V, &V) -> E,
+    {
+        let mut graph: BTreeGraph<V, E> = BTreeGraph::new();
+        let mut listed: BTreeSet<V> = BTreeSet::new();
+        for (line, text) in lines(input) {
+            let (x, targets) = match text.find(':') {
+                Some(n) => (text[..n].trim_end(), &text[n + 1..]),
+                None => return Err(error(line, "expected a vertex followed by ':'")),
+            };
+            let x: V = parse(x, line, "vertex is not valid")?;
+            if !listed.insert(x.clone()) {
+                return Err(error(line, "vertex is listed more than once"));
+            }
+            graph.add_vertex(x.clone());
+            for y in fields(targets).iter() {
+                let y: V = parse(y, line, "target vertex is not valid")?;
+                let e = edge(&x, &y);
+                if graph.edges.contains_key(&e) {
+                    return Err(error(line, "edge is named more than once"));
+                }
+                graph.add_edge_with_vertices(x.clone(), y, e);
+            }
+        }
+        Ok(graph)
+    }
+}
+
+fn error(line: usize, message: &'static str) -> ParseError {
+    ParseError { line, message }
+}
+
+fn parse<T: FromStr>(field: &str, line: usize, message: &'static str) -> Result<T, ParseError> {
+    field.parse().map_err(|_| error(line, message))
+}
+
+/// Each line which is not blank, or a comment, along with its number.
+fn lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+}
+
+fn fields(text: &str) -> Vec<&str> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|field| !field.is_empty())
+        .collect()
+}
@@ -0,0 +1,166 @@
+use alloc::collections::BTreeSet;
+
+use super::undo::UndoLog;
+use crate::{
+    AddEdge, AddVertex, Adjacent, BTreeGraph, Connections, Edges, Error, GetEdgeValue,
+    GetVertexValue, RemoveEdge, RemoveVertex, RemovedEdges, Vertices,
+};
+
+/// `Transaction` is a handle to a `BTreeGraph` which records every mutation made through
+/// it, so all of them can be rolled back at once. See `BTreeGraph::transaction`.
+pub struct Transaction<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    graph: &'g mut BTreeGraph<V, E>,
+    log: UndoLog<V, E>,
+}
+
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    /// Runs f against a transaction on the graph. If f returns an error, every mutation
+    /// made through the transaction is rolled back, leaving the graph as it was, and the
+    /// error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, AddEdge, Error, Vertices};
+    /// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+    ///
+    /// let result: Result<(), Error> = graph.transaction(|tx| {
+    ///     tx.add_vertex(String::from("origin"));
+    ///     tx.add_vertex(String::from("destination"));
+    ///     tx.add_edge(String::from("origin"), String::from("destination"), 10)?;
+    ///     // The vertex "nowhere" does not exist so this fails.
+    ///     tx.add_edge(String::from("origin"), String::from("nowhere"), 20)?;
+    ///     Ok(())
+    /// });
+    ///
+    /// assert_eq!(result.unwrap_err(), Error::VertexDoesNotExist);
+    /// assert_eq!(graph.vertices().len(), 0);
+    /// ```
+    pub fn transaction<F, T, X>(&mut self, f: F) -> Result<T, X>
+    where
+        F: FnOnce(&mut Transaction<V, E>) -> Result<T, X>,
+    {
+        let mut tx = Transaction {
+            graph: self,
+            log: UndoLog::new(),
+        };
+        let result = f(&mut tx);
+        if result.is_err() {
+            tx.log.rollback(tx.graph);
+        }
+        result
+    }
+}
+
+impl<'g, V, E> AddVertex<V> for Transaction<'g, V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    fn add_vertex(&mut self, x: V) -> bool {
+        self.log.add_vertex(self.graph, x)
+    }
+}
+
+impl<'g, V, E> AddEdge<V, E> for Transaction<'g, V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    type Error = Error;
+    fn add_edge(&mut self, x: V, y: V, e: E) -> Result<Option<(V, V)>, Self::Error> {
+        self.log.add_edge(self.graph, x, y, e)
+    }
+}
+
+impl<'g, V, E> RemoveEdge<V, E> for Transaction<'g, V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    type Error = Error;
+    fn remove_edge(&mut self, e: E) -> Result<(V, V), Self::Error> {
+        self.log.remove_edge(self.graph, e)
+    }
+}
+
+impl<'g, V, E> RemoveVertex<V, E> for Transaction<'g, V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    type Error = Error;
+    fn remove_vertex(&mut self, v: V) -> Result<RemovedEdges<V, E>, Self::Error> {
+        self.log.remove_vertex(self.graph, v)
+    }
+}
+
+impl<'g, V, E> Vertices<V> for Transaction<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn vertices(&self) -> BTreeSet<&V> {
+        self.graph.vertices()
+    }
+}
+
+impl<'g, V, E> Edges<E> for Transaction<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn edges(&self) -> BTreeSet<&E> {
+        self.graph.edges()
+    }
+}
+
+impl<'g, V, E> GetEdgeValue<V, E> for Transaction<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn get_edge_value(&self, e: E) -> Option<&(V, V)> {
+        self.graph.get_edge_value(e)
+    }
+}
+
+impl<'g, V, E> GetVertexValue<V, E> for Transaction<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn get_vertex_value(&self, v: V) -> Option<&BTreeSet<E>> {
+        self.graph.get_vertex_value(v)
+    }
+}
+
+impl<'g, V, E> Adjacent<V> for Transaction<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    type Error = Error;
+    fn adjacent(&self, x: V, y: V) -> Result<bool, Self::Error> {
+        self.graph.adjacent(x, y)
+    }
+}
+
+impl<'g, V, E> Connections<V> for Transaction<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    type Error = Error;
+    fn connections(&self, v: V) -> Result<BTreeSet<&V>, Self::Error> {
+        self.graph.connections(v)
+    }
+}
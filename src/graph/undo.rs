@@ -0,0 +1,134 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::{AddEdge, AddVertex, BTreeGraph, Error, RemoveEdge, RemoveVertex, RemovedEdges};
+
+/// `Undo` records the value an entry of one of the graph's maps held before a mutation
+/// touched it, so the mutation can be reverted.
+enum Undo<V, E> {
+    /// The adjacency list of the vertex, or none if the vertex did not exist.
+    Vertex(V, Option<BTreeSet<E>>),
+    /// Whether the edge was in the adjacency list of the vertex.
+    Adjacency(V, E, bool),
+    /// The value of the edge, or none if the edge did not exist.
+    Edge(E, Option<(V, V)>),
+}
+
+/// `UndoLog` makes mutations to a graph while recording what they overwrite, so that they
+/// can all be rolled back at once. Mutations which fail are not recorded as they leave
+/// the graph untouched.
+pub(crate) struct UndoLog<V, E>(Vec<Undo<V, E>>);
+
+impl<V, E> UndoLog<V, E>
+where
+    V: Ord + Clone,
+    E: Ord + Clone,
+{
+    pub(crate) fn new() -> Self {
+        UndoLog(Vec::new())
+    }
+
+    pub(crate) fn add_vertex(&mut self, graph: &mut BTreeGraph<V, E>, x: V) -> bool {
+        // Adding a vertex which is there leaves the graph untouched.
+        if !graph.vertices.contains_key(&x) {
+            self.0.push(Undo::Vertex(x.clone(), None));
+        }
+        graph.add_vertex(x)
+    }
+
+    pub(crate) fn add_edge(
+        &mut self,
+        graph: &mut BTreeGraph<V, E>,
+        x: V,
+        y: V,
+        e: E,
+    ) -> Result<Option<(V, V)>, Error> {
+        if graph.vertices.contains_key(&y) {
+            if let Some(edges) = graph.vertices.get(&x) {
+                let present = edges.contains(&e);
+                self.0.push(Undo::Adjacency(x.clone(), e.clone(), present));
+                // An existing edge may move to another source, leaving the old adjacency list.
+                match graph.edges.get(&e) {
+                    Some(value) => self.record_edge(graph, &e, value),
+                    None => self.0.push(Undo::Edge(e.clone(), None)),
+                }
+            }
+        }
+        graph.add_edge(x, y, e)
+    }
+
+    pub(crate) fn remove_edge(
+        &mut self,
+        graph: &mut BTreeGraph<V, E>,
+        e: E,
+    ) -> Result<(V, V), Error> {
+        if let Some(value) = graph.edges.get(&e) {
+            self.record_edge(graph, &e, value);
+        }
+        graph.remove_edge(e)
+    }
+
+    pub(crate) fn remove_vertex(
+        &mut self,
+        graph: &mut BTreeGraph<V, E>,
+        v: V,
+    ) -> Result<RemovedEdges<V, E>, Error> {
+        if let Some(edges) = graph.vertices.get(&v) {
+            self.0.push(Undo::Vertex(v.clone(), Some(edges.clone())));
+            for (e, value) in graph.edges.iter() {
+                if edges.contains(e) {
+                    self.0.push(Undo::Edge(e.clone(), Some(value.clone())));
+                } else if value.1 == v {
+                    self.record_edge(graph, e, value);
+                }
+            }
+        }
+        graph.remove_vertex(v)
+    }
+
+    /// Records an existing edge, and its place in the adjacency list of its source.
+    fn record_edge(&mut self, graph: &BTreeGraph<V, E>, e: &E, value: &(V, V)) {
+        let present = graph
+            .vertices
+            .get(&value.0)
+            .is_some_and(|edges| edges.contains(e));
+        self.0
+            .push(Undo::Adjacency(value.0.clone(), e.clone(), present));
+        self.0.push(Undo::Edge(e.clone(), Some(value.clone())));
+    }
+}
+
+impl<V, E> UndoLog<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    /// Reverts every recorded mutation, most recent first.
+    pub(crate) fn rollback(self, graph: &mut BTreeGraph<V, E>) {
+        for undo in self.0.into_iter().rev() {
+            match undo {
+                Undo::Vertex(v, Some(edges)) => {
+                    graph.vertices.insert(v, edges);
+                }
+                Undo::Vertex(v, None) => {
+                    graph.vertices.remove(&v);
+                }
+                Undo::Adjacency(v, e, present) => {
+                    if let Some(edges) = graph.vertices.get_mut(&v) {
+                        if present {
+                            edges.insert(e);
+                        } else {
+                            edges.remove(&e);
+                        }
+                    }
+                }
+                Undo::Edge(e, Some(value)) => {
+                    graph.edges.insert(e, value);
+                }
+                Undo::Edge(e, None) => {
+                    graph.edges.remove(&e);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use alloc::vec::Vec;
+
+use crate::BTreeGraph;
+
+/// `Violation` is a way in which the vertex, and edge maps of a graph disagree.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Violation<V, E> {
+    /// The source vertex of the edge does not exist (the edge, and the vertex).
+    MissingSource(E, V),
+    /// The target vertex of the edge does not exist (the edge, and the vertex).
+    MissingTarget(E, V),
+    /// The adjacency list of the vertex lists an edge which does not exist.
+    MissingEdge(V, E),
+    /// The adjacency list of the vertex lists an edge with another source.
+    WrongSource(V, E),
+    /// The edge is missing from the adjacency list of its source.
+    Unlisted(E),
+}
+
+impl<V, E> BTreeGraph<V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    /// Checks the vertex, and edge maps of the graph agree. Every edge must have vertices
+    /// which exist, and appear in the adjacency list of its source, and no other. Returns
+    /// every violation found, or none if the graph is valid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use btree_graph::{BTreeGraph, AddVertex, AddEdge};
+    /// let mut graph: BTreeGraph<String, usize> = BTreeGraph::new();
+    /// graph.add_vertex(String::from("origin"));
+    /// graph.add_vertex(String::from("destination"));
+    /// graph.add_edge(String::from("origin"), String::from("destination"), 10);
+    ///
+    /// assert!(graph.validate().is_empty());
+    /// ```
+    pub fn validate(&self) -> Vec<Violation<&V, &E>> {
+        let mut violations: Vec<Violation<&V, &E>> = Vec::new();
+        for (e, (x, y)) in self.edges.iter() {
+            match self.vertices.get(x) {
+                Some(edges) => {
+                    if !edges.contains(e) {
+                        violations.push(Violation::Unlisted(e));
+                    }
+                }
+                None => violations.push(Violation::MissingSource(e, x)),
+            }
+            if !self.vertices.contains_key(y) {
+                violations.push(Violation::MissingTarget(e, y));
+            }
+        }
+        for (v, edges) in self.vertices.iter() {
+            for e in edges.iter() {
+                match self.edges.get(e) {
+                    Some((x, _)) => {
+                        if x != v {
+                            violations.push(Violation::WrongSource(v, e));
+                        }
+                    }
+                    None => violations.push(Violation::MissingEdge(v, e)),
+                }
+            }
+        }
+        violations
+    }
+}
@@ -0,0 +1,484 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::cell::OnceCell;
+
+use crate::{Adjacent, BTreeGraph, Connections, Edges, Error, GetEdgeValue, Vertices};
+
+/// `Reversed` is a borrowed view of a `BTreeGraph` in which the direction of every edge
+/// is flipped. Creating the view is O(1); the indexes it needs are built on first use.
+/// `Connections` builds an index of references to the incoming edges of each vertex in
+/// O(V + E), cloning nothing. `GetEdgeValue` builds the flipped value of every edge in
+/// O(E), cloning both vertices of each edge.
+pub struct Reversed<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    graph: &'g BTreeGraph<V, E>,
+    incoming: OnceCell<BTreeMap<&'g V, BTreeSet<&'g E>>>,
+    values: OnceCell<BTreeMap<&'g E, (V, V)>>,
+}
+
+impl<'g, V, E> Reversed<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    pub fn new(graph: &'g BTreeGraph<V, E>) -> Self {
+        Reversed {
+            graph,
+            incoming: OnceCell::new(),
+            values: OnceCell::new(),
+        }
+    }
+
+    /// Returns the incoming edges of each vertex, building the index on first use.
+    fn incoming(&self) -> &BTreeMap<&'g V, BTreeSet<&'g E>> {
+        self.incoming.get_or_init(|| {
+            let mut incoming: BTreeMap<&'g V, BTreeSet<&'g E>> = self
+                .graph
+                .vertices
+                .keys()
+                .map(|v| (v, BTreeSet::new()))
+                .collect();
+            for (edge, (_, to)) in self.graph.edges.iter() {
+                incoming.entry(to).or_default().insert(edge);
+            }
+            incoming
+        })
+    }
+}
+
+impl<'g, V, E> Vertices<V> for Reversed<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn vertices(&self) -> BTreeSet<&V> {
+        self.graph.vertices()
+    }
+}
+
+impl<'g, V, E> Edges<E> for Reversed<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn edges(&self) -> BTreeSet<&E> {
+        self.graph.edges()
+    }
+}
+
+/// Returns the endpoints of the edge e as seen through the view, i.e. (y, x) for an edge
+/// (x, y) of the underlying graph. The flipped values are cloned on first use.
+impl<'g, V, E> GetEdgeValue<V, E> for Reversed<'g, V, E>
+where
+    V: Ord + Clone,
+    E: Ord,
+{
+    fn get_edge_value(&self, e: E) -> Option<&(V, V)> {
+        self.values
+            .get_or_init(|| {
+                self.graph
+                    .edges
+                    .iter()
+                    .map(|(edge, (from, to))| (edge, (to.clone(), from.clone())))
+                    .collect()
+            })
+            .get(&e)
+    }
+}
+
+impl<'g, V, E> Adjacent<V> for Reversed<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    type Error = Error;
+    fn adjacent(&self, x: V, y: V) -> Result<bool, Self::Error> {
+        self.graph.adjacent(y, x)
+    }
+}
+
+impl<'g, V, E> Connections<V> for Reversed<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    type Error = Error;
+    fn connections(&self, v: V) -> Result<BTreeSet<&V>, Self::Error> {
+        match self.incoming().get(&v) {
+            Some(edges) => Ok(edges
+                .iter()
+                .map(|edge| -> &V { &self.graph.edges.get(*edge).unwrap().0 })
+                .collect()),
+            None => Err(Error::VertexDoesNotExist),
+        }
+    }
+}
+
+/// `Filtered` is a borrowed view of a `BTreeGraph` which hides every vertex rejected by
+/// the vertex predicate, and every edge rejected by the edge predicate or incident to a
+/// hidden vertex.
+pub struct Filtered<'g, V, E, FV, FE>
+where
+    V: Ord,
+    E: Ord,
+    FV: Fn(&V) -> bool,
+    FE: Fn(&E, &(V, V)) -> bool,
+{
+    graph: &'g BTreeGraph<V, E>,
+    vertex_pred: FV,
+    edge_pred: FE,
+}
+
+impl<'g, V, E, FV, FE> Filtered<'g, V, E, FV, FE>
+where
+    V: Ord,
+    E: Ord,
+    FV: Fn(&V) -> bool,
+    FE: Fn(&E, &(V, V)) -> bool,
+{
+    pub fn new(graph: &'g BTreeGraph<V, E>, vertex_pred: FV, edge_pred: FE) -> Self {
+        Filtered {
+            graph,
+            vertex_pred,
+            edge_pred,
+        }
+    }
+}
+
+/// `SubgraphView` is a borrowed view of the subgraph of a `BTreeGraph` induced by a set
+/// of vertices. Vertices in the set which do not exist in the graph are ignored.
+pub struct SubgraphView<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    graph: &'g BTreeGraph<V, E>,
+    vertices: &'g BTreeSet<V>,
+}
+
+impl<'g, V, E> SubgraphView<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    pub fn new(graph: &'g BTreeGraph<V, E>, vertices: &'g BTreeSet<V>) -> Self {
+        SubgraphView { graph, vertices }
+    }
+}
+
+/// `Mask` is implemented by the views which only hide parts of the underlying graph, so
+/// the read traits can be implemented once in terms of what is visible.
+trait Mask<'g, V, E>
+where
+    V: Ord + 'g,
+    E: Ord + 'g,
+{
+    fn graph(&self) -> &'g BTreeGraph<V, E>;
+    fn has_vertex(&self, v: &V) -> bool;
+    fn has_edge(&self, e: &E, value: &(V, V)) -> bool;
+}
+
+impl<'g, V, E, FV, FE> Mask<'g, V, E> for Filtered<'g, V, E, FV, FE>
+where
+    V: Ord,
+    E: Ord,
+    FV: Fn(&V) -> bool,
+    FE: Fn(&E, &(V, V)) -> bool,
+{
+    fn graph(&self) -> &'g BTreeGraph<V, E> {
+        self.graph
+    }
+
+    fn has_vertex(&self, v: &V) -> bool {
+        (self.vertex_pred)(v)
+    }
+
+    fn has_edge(&self, e: &E, value: &(V, V)) -> bool {
+        (self.edge_pred)(e, value)
+    }
+}
+
+impl<'g, V, E> Mask<'g, V, E> for SubgraphView<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn graph(&self) -> &'g BTreeGraph<V, E> {
+        self.graph
+    }
+
+    fn has_vertex(&self, v: &V) -> bool {
+        self.vertices.contains(v)
+    }
+
+    fn has_edge(&self, _: &E, _: &(V, V)) -> bool {
+        true
+    }
+}
+
+fn masked_vertex<'g, V, E, M>(m: &M, v: &V) -> Option<&'g BTreeSet<E>>
+where
+    V: Ord + 'g,
+    E: Ord + 'g,
+    M: Mask<'g, V, E>,
+{
+    m.graph().vertices.get(v).filter(|_| m.has_vertex(v))
+}
+
+fn masked_edge<'g, V, E, M>(m: &M, e: &E) -> Option<&'g (V, V)>
+where
+    V: Ord + 'g,
+    E: Ord + 'g,
+    M: Mask<'g, V, E>,
+{
+    m.graph()
+        .edges
+        .get(e)
+        .filter(|value| m.has_vertex(&value.0) && m.has_vertex(&value.1) && m.has_edge(e, value))
+}
+
+fn masked_vertices<'g, V, E, M>(m: &M) -> BTreeSet<&'g V>
+where
+    V: Ord + 'g,
+    E: Ord + 'g,
+    M: Mask<'g, V, E>,
+{
+    m.graph()
+        .vertices
+        .keys()
+        .filter(|v| m.has_vertex(v))
+        .collect()
+}
+
+fn masked_edges<'g, V, E, M>(m: &M) -> BTreeSet<&'g E>
+where
+    V: Ord + 'g,
+    E: Ord + 'g,
+    M: Mask<'g, V, E>,
+{
+    m.graph()
+        .edges
+        .keys()
+        .filter(|e| masked_edge(m, e).is_some())
+        .collect()
+}
+
+fn masked_adjacent<'g, V, E, M>(m: &M, x: V, y: V) -> Result<bool, Error>
+where
+    V: Ord + 'g,
+    E: Ord + 'g,
+    M: Mask<'g, V, E>,
+{
+    if masked_vertex(m, &y).is_some() {
+        if let Some(edges) = masked_vertex(m, &x) {
+            return Ok(edges
+                .iter()
+                .filter_map(|edge| masked_edge(m, edge))
+                .any(|e| e.1 == y));
+        }
+    }
+    Err(Error::VertexDoesNotExist)
+}
+
+fn masked_connections<'g, V, E, M>(m: &M, v: V) -> Result<BTreeSet<&'g V>, Error>
+where
+    V: Ord + 'g,
+    E: Ord + 'g,
+    M: Mask<'g, V, E>,
+{
+    match masked_vertex(m, &v) {
+        Some(edges) => Ok(edges
+            .iter()
+            .filter_map(|edge| masked_edge(m, edge))
+            .map(|e| -> &V { &e.1 })
+            .collect()),
+        None => Err(Error::VertexDoesNotExist),
+    }
+}
+
+impl<'g, V, E, FV, FE> Vertices<V> for Filtered<'g, V, E, FV, FE>
+where
+    V: Ord,
+    E: Ord,
+    FV: Fn(&V) -> bool,
+    FE: Fn(&E, &(V, V)) -> bool,
+{
+    fn vertices(&self) -> BTreeSet<&V> {
+        masked_vertices(self)
+    }
+}
+
+impl<'g, V, E, FV, FE> Edges<E> for Filtered<'g, V, E, FV, FE>
+where
+    V: Ord,
+    E: Ord,
+    FV: Fn(&V) -> bool,
+    FE: Fn(&E, &(V, V)) -> bool,
+{
+    fn edges(&self) -> BTreeSet<&E> {
+        masked_edges(self)
+    }
+}
+
+impl<'g, V, E, FV, FE> GetEdgeValue<V, E> for Filtered<'g, V, E, FV, FE>
+where
+    V: Ord,
+    E: Ord,
+    FV: Fn(&V) -> bool,
+    FE: Fn(&E, &(V, V)) -> bool,
+{
+    fn get_edge_value(&self, e: E) -> Option<&(V, V)> {
+        masked_edge(self, &e)
+    }
+}
+
+impl<'g, V, E, FV, FE> Adjacent<V> for Filtered<'g, V, E, FV, FE>
+where
+    V: Ord,
+    E: Ord,
+    FV: Fn(&V) -> bool,
+    FE: Fn(&E, &(V, V)) -> bool,
+{
+    type Error = Error;
+    fn adjacent(&self, x: V, y: V) -> Result<bool, Self::Error> {
+        masked_adjacent(self, x, y)
+    }
+}
+
+impl<'g, V, E, FV, FE> Connections<V> for Filtered<'g, V, E, FV, FE>
+where
+    V: Ord,
+    E: Ord,
+    FV: Fn(&V) -> bool,
+    FE: Fn(&E, &(V, V)) -> bool,
+{
+    type Error = Error;
+    fn connections(&self, v: V) -> Result<BTreeSet<&V>, Self::Error> {
+        masked_connections(self, v)
+    }
+}
+
+impl<'g, V, E> Vertices<V> for SubgraphView<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn vertices(&self) -> BTreeSet<&V> {
+        masked_vertices(self)
+    }
+}
+
+impl<'g, V, E> Edges<E> for SubgraphView<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn edges(&self) -> BTreeSet<&E> {
+        masked_edges(self)
+    }
+}
+
+impl<'g, V, E> GetEdgeValue<V, E> for SubgraphView<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    fn get_edge_value(&self, e: E) -> Option<&(V, V)> {
+        masked_edge(self, &e)
+    }
+}
+
+impl<'g, V, E> Adjacent<V> for SubgraphView<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    type Error = Error;
+    fn adjacent(&self, x: V, y: V) -> Result<bool, Self::Error> {
+        masked_adjacent(self, x, y)
+    }
+}
+
+impl<'g, V, E> Connections<V> for SubgraphView<'g, V, E>
+where
+    V: Ord,
+    E: Ord,
+{
+    type Error = Error;
+    fn connections(&self, v: V) -> Result<BTreeSet<&V>, Self::Error> {
+        masked_connections(self, v)
+    }
+}
+
+#[cfg(feature = "fmt")]
+fn write_masked<'g, V, E, M>(m: &M, f: &mut core::fmt::Formatter) -> core::fmt::Result
+where
+    V: Ord + core::fmt::Display + 'g,
+    E: Ord + core::fmt::Display + 'g,
+    M: Mask<'g, V, E>,
+{
+    crate::graph::display::write_table(
+        f,
+        m.graph()
+            .vertices
+            .iter()
+            .filter(|(v, _)| m.has_vertex(v))
+            .map(|(v, edges)| {
+                let edges = edges
+                    .iter()
+                    .filter_map(|e| masked_edge(m, e).map(|(_, y)| (e, y)))
+                    .collect();
+                (v, edges)
+            }),
+    )
+}
+
+/// Displays the view as an adjacency list table, as with `BTreeGraph`, with each edge
+/// listed under the vertex it goes from in the view.
+#[cfg(feature = "fmt")]
+impl<'g, V, E> core::fmt::Display for Reversed<'g, V, E>
+where
+    V: Ord + core::fmt::Display,
+    E: Ord + core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        crate::graph::display::write_table(
+            f,
+            self.incoming().iter().map(|(v, edges)| {
+                let edges = edges
+                    .iter()
+                    .map(|e| (*e, &self.graph.edges.get(*e).unwrap().0))
+                    .collect();
+                (*v, edges)
+            }),
+        )
+    }
+}
+
+/// Displays the view as an adjacency list table, as with `BTreeGraph`.
+#[cfg(feature = "fmt")]
+impl<'g, V, E, FV, FE> core::fmt::Display for Filtered<'g, V, E, FV, FE>
+where
+    V: Ord + core::fmt::Display,
+    E: Ord + core::fmt::Display,
+    FV: Fn(&V) -> bool,
+    FE: Fn(&E, &(V, V)) -> bool,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write_masked(self, f)
+    }
+}
+
+/// Displays the view as an adjacency list table, as with `BTreeGraph`.
+#[cfg(feature = "fmt")]
+impl<'g, V, E> core::fmt::Display for SubgraphView<'g, V, E>
+where
+    V: Ord + core::fmt::Display,
+    E: Ord + core::fmt::Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write_masked(self, f)
+    }
+}